@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde::de::Deserializer;
 
 use crate::error::{KError, KrakenErrors};
+use crate::trace;
 
 // TODO: Query AssetInfo endpoint and write script to fill out the
 // enum and trait impl
@@ -277,7 +278,7 @@ impl FromStr for KAsset {
             "XTZ" => Ok(KAsset::XTZ),
             "XTZ.S" => Ok(KAsset::XtzS),
             "YFI" => Ok(KAsset::YFI),
-            _     => Err(KrakenErrors(vec![KError::AssetParseError])),
+            _     => Err(KrakenErrors(vec![KError::AssetParseError(val.to_string())])),
         }
     }
 }
@@ -346,14 +347,12 @@ impl FromStr for KAssetPair {
         match val.len() {
             // We know this is pairs with KAsset::SC as the base currency. A 2/3 split
             5 => {
-                println!("base: {}, quote: {}\n", (&val[..2]), (&val[2..]));
                 Ok(KAssetPair((&val[..2]).parse::<KAsset>()?, (&val[2..]).parse::<KAsset>()?))
             },
 
             // Has to be split 3/3. It can't be split 2/4 since that would imply SC is the base
             // currency but we know all pairs with SC are of length 5
             6 => {
-                println!("base: {}, quote: {}\n", (&val[..3]), (&val[3..]));
                 Ok(KAssetPair((&val[..3]).parse::<KAsset>()?, (&val[3..]).parse::<KAsset>()?))
             },
 
@@ -365,7 +364,6 @@ impl FromStr for KAssetPair {
                         (&val[4..]).parse::<KAsset>()
                     )
                 {
-                    println!("base: {}, quote: {}\n", &val[..4], &val[4..]);
                     Ok(KAssetPair(base, quote))
                 } else {
                     if let (Ok(base), Ok(quote)) = 
@@ -376,9 +374,8 @@ impl FromStr for KAssetPair {
                     {
                         Ok(KAssetPair(base, quote))
                     } else {
-                        println!("Length 7: {}", val);
-                        println!("base: {}, quote: {}\n", &val[..3], &val[3..]);
-                        Err(KrakenErrors(vec![KError::AssetParseError]))
+                        trace::log_debug!(pair = val, "unknown asset pair");
+                        Err(KrakenErrors(vec![KError::AssetParseError(val.to_string())]))
                     }
                 }
             },
@@ -392,7 +389,6 @@ impl FromStr for KAssetPair {
                         (&val[4..]).parse::<KAsset>()
                     )
                 {
-                    println!("base: {}, quote: {}\n", &val[..4], &val[4..]);
                     Ok(KAssetPair(base, quote))
                 } else { 
                     if let (Ok(base), Ok(quote)) = 
@@ -401,7 +397,6 @@ impl FromStr for KAssetPair {
                             (&val[5..]).parse::<KAsset>()
                         )
                     {
-                        println!("base: {}, quote: {}\n", &val[..5], &val[5..]);
                         Ok(KAssetPair(base, quote))
                     } else {
                         if let (Ok(base), Ok(quote)) =
@@ -412,9 +407,8 @@ impl FromStr for KAssetPair {
                         {
                             Ok(KAssetPair(base, quote))
                         } else {
-                            println!("Length 8: {}", val);
-                            println!("base: {}, quote: {}\n", &val[..3], &val[3..]);
-                            Err(KrakenErrors(vec![KError::AssetParseError]))
+                            trace::log_debug!(pair = val, "unknown asset pair");
+                            Err(KrakenErrors(vec![KError::AssetParseError(val.to_string())]))
                         }
                     }
                 }
@@ -430,8 +424,8 @@ impl FromStr for KAssetPair {
             }
             // We don't know what we got, Kraken probably changed their api if we are hitting this
             _ => {
-                println!("Unknown pair: {}\n", &val);
-                Err(KrakenErrors(vec![KError::UnknownAssetPair]))
+                trace::log_debug!(pair = val, "unknown asset pair");
+                Err(KrakenErrors(vec![KError::AssetParseError(val.to_string())]))
             },
         }
     }
@@ -0,0 +1,154 @@
+//! Module for sending requests to endpoints this crate doesn't have builders for yet
+//!
+//! [KICustom] builds a [KrakenInput] for any endpoint from its name, whether it is public or
+//! private and its parameters. Requests are signed and sent the same way as for the built in
+//! endpoints. The output type is chosen by the caller: either a type of your own implementing
+//! [Output] and [Deserialize][serde::Deserialize], or [serde_json::Value] to get the raw json
+//!
+//! ```no_run
+//! # use kraapi::client::KrakenClient;
+//! # use kraapi::api::MethodType;
+//! # use kraapi::api::custom::KICustom;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = KrakenClient::new("<Your_API_Key>", "<Your_API_Secret>");
+//!
+//! let input = KICustom::<serde_json::Value>::build(MethodType::Private, "Earn/Strategies")
+//!     .with_param("asset", "DOT");
+//! let strategies = client.send(input).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for an arbitrary endpoint, returning the output type `O`
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(bound = "")]
+pub struct KICustom<O> {
+    methodtype: MethodType,
+    endpoint: String,
+    params: IndexMap<String, String>,
+    #[serde(skip)]
+    output: PhantomData<fn() -> O>,
+}
+
+impl<O> KICustom<O> {
+    /// Constructor returning a [KrakenInput] builder for a custom endpoint.
+    /// * `methodtype` is whether the endpoint is public or private
+    /// * `endpoint` is the name of the endpoint as it appears in the url, e.g. `Earn/Strategies`
+    ///   for `/0/private/Earn/Strategies`
+    pub fn build(methodtype: MethodType, endpoint: &str) -> Self {
+        KICustom {
+            methodtype,
+            endpoint: String::from(endpoint),
+            params: IndexMap::new(),
+            output: PhantomData,
+        }
+    }
+
+    /// Set the parameter `key` to `value`, overwriting any previous value
+    pub fn with_param<T: Display>(self, key: &str, value: T) -> Self {
+        self.update_input(key, value)
+    }
+
+    /// Set every parameter in `params`, overwriting any previous values
+    pub fn with_params<I, K, V>(self, params: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Display,
+    {
+        params.into_iter().fold(self, |input, (key, value)| {
+            input.with_param(key.as_ref(), value)
+        })
+    }
+
+    /// Remove the parameter `key` from the request
+    pub fn clear_param(mut self, key: &str) -> Self {
+        self.params.shift_remove(key);
+        self
+    }
+}
+
+impl<O> MutateInput for KICustom<O> {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl<O> UpdateInput for KICustom<O> {}
+
+impl<O: Output> Input for KICustom<O> {
+    type Output = O;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: self.methodtype,
+                endpoint: self.endpoint,
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: self.methodtype,
+                    endpoint: self.endpoint.clone(),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::format_params;
+    use serde_json::Value;
+
+    #[test]
+    fn custom_input() {
+        let (input, template) = KICustom::<Value>::build(MethodType::Private, "Earn/Strategies")
+            .with_params([("asset", "DOT"), ("limit", "10")])
+            .with_param("limit", 20)
+            .finish_clone();
+        assert_eq!(input.info().endpoint(), "Earn/Strategies");
+        assert_eq!(input.info().method(), &MethodType::Private);
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "asset=DOT&limit=20"
+        );
+
+        let input = template.clear_param("asset").finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "limit=20"
+        );
+
+        // Parameters of endpoints without a builder are sent as given, commas and all
+        let input = KICustom::<Value>::build(MethodType::Private, "Earn/Transfers")
+            .with_param("ids[]", "1,2")
+            .finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "ids%5B%5D=1%2C2"
+        );
+    }
+}
@@ -4,12 +4,78 @@ use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
 
 use super::error::{KError, KrakenErrors};
+use crate::trace::RedactedParams;
+
+// Implement Deserialize for an input builder from the map of parameters it sends to Kraken. Each
+// listed parameter, one the builder can set, is paired with the type its setter takes and its
+// value must parse back into that type. Parameters the builder's build() sets must be present
+// too, so that a stored template is checked before it is sent instead of failing at Kraken
+macro_rules! deserialize_params {
+    (
+        $builder:ident,
+        [$($param:literal => $kind:ty),* $(,)?]
+        $(, required = [$($required:literal),* $(,)?])?
+        $(,)?
+    ) => {
+        impl<'de> ::serde::Deserialize<'de> for $builder {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                const PARAMS: &[&str] = &[$($param),*];
+                const REQUIRED: &[&str] = &[$($($required),*)?];
+                let params: ::indexmap::map::IndexMap<String, String> =
+                    ::serde::Deserialize::deserialize(deserializer)?;
+                for (key, value) in &params {
+                    // An empty value is how setters clear a list or an optional flag
+                    let valid = match key.as_str() {
+                        $($param => Some(
+                            value.is_empty() || <$kind as $crate::api::ParamValue>::is_valid(value)
+                        ),)*
+                        _ => None,
+                    };
+                    match valid {
+                        Some(true) => (),
+                        Some(false) => {
+                            return Err(::serde::de::Error::invalid_value(
+                                ::serde::de::Unexpected::Str(value.as_str()),
+                                &format!("a valid value for {}", key).as_str(),
+                            ))
+                        }
+                        None => return Err(::serde::de::Error::unknown_field(key, PARAMS)),
+                    }
+                }
+                match REQUIRED
+                    .iter()
+                    .find(|key| params.get(**key).filter(|value| !value.is_empty()).is_none())
+                {
+                    Some(key) => Err(::serde::de::Error::missing_field(*key)),
+                    None => Ok($builder { params }),
+                }
+            }
+        }
+    };
+}
+
+// Implement ParamValue for an enum by comparing against the parameter each listed variant sets
+macro_rules! variant_params {
+    ($kind:ty, [$($variant:expr),* $(,)?]) => {
+        impl $crate::api::ParamValue for $kind {
+            fn is_valid(value: &str) -> bool {
+                [$($variant),*].iter().any(|variant| variant.to_string() == value)
+            }
+        }
+    };
+}
 
 pub mod asset;
+pub mod custom;
 pub mod private;
 pub mod public;
+pub mod timestamp;
 
 /// Result alias. Either contains the output struct of some type `T` that implements [Output]
 /// or [KrakenErrors][super::error::KrakenErrors]`s (a custom collection of
@@ -24,8 +90,127 @@ pub(crate) struct KResult<T> {
     pub error: Vec<String>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub(crate) enum MethodType {
+// Type a builder parameter is set from, used when deserializing a builder to check that each
+// value is one a setter could have sent
+pub(crate) trait ParamValue {
+    fn is_valid(value: &str) -> bool;
+}
+
+// Lists are sent as comma separated values
+impl<T: ParamValue> ParamValue for [T] {
+    fn is_valid(value: &str) -> bool {
+        value.split(',').all(T::is_valid)
+    }
+}
+
+// Free form text such as ids, descriptions and prices, which Kraken checks itself
+impl ParamValue for String {
+    fn is_valid(_: &str) -> bool {
+        true
+    }
+}
+
+// Timestamps are sent as whole Unix seconds
+impl ParamValue for timestamp::KTimestamp {
+    fn is_valid(value: &str) -> bool {
+        value.parse::<i64>().is_ok()
+    }
+}
+
+// Implement ParamValue for types whose setters send their FromStr representation
+macro_rules! parsed_params {
+    ($($kind:ty),* $(,)?) => {
+        $(impl ParamValue for $kind {
+            fn is_valid(value: &str) -> bool {
+                value.parse::<$kind>().is_ok()
+            }
+        })*
+    };
+}
+
+parsed_params!(bool, u32, u64, i64, f64, asset::KAsset, asset::KAssetPair);
+
+variant_params!(
+    OHLCInterval,
+    [
+        OHLCInterval::One,
+        OHLCInterval::Five,
+        OHLCInterval::Fifteen,
+        OHLCInterval::Thirty,
+        OHLCInterval::Sixty,
+        OHLCInterval::TwoForty,
+        OHLCInterval::FourteenForty,
+        OHLCInterval::TenEighty,
+        OHLCInterval::TwentyoneSixty,
+    ]
+);
+variant_params!(
+    OrderCloseTime,
+    [OrderCloseTime::Open, OrderCloseTime::Close, OrderCloseTime::Both]
+);
+variant_params!(
+    TradeHistoryType,
+    [
+        TradeHistoryType::All,
+        TradeHistoryType::PosAny,
+        TradeHistoryType::PosClosed,
+        TradeHistoryType::PosClosing,
+        TradeHistoryType::PosNone,
+    ]
+);
+variant_params!(
+    LedgerType,
+    [
+        LedgerType::All,
+        LedgerType::Deposit,
+        LedgerType::Withdrawal,
+        LedgerType::Trade,
+        LedgerType::Margin,
+    ]
+);
+variant_params!(TradeType, [TradeType::Buy, TradeType::Sell]);
+// Only the name of the order type is sent as the order type, its prices are separate parameters
+variant_params!(
+    OrderType,
+    [
+        Market,
+        Limit(String::new()),
+        StopLoss(String::new()),
+        TakeProfit(String::new()),
+        StopLossLimit(String::new(), String::new()),
+        TakeProfitLimit(String::new(), String::new()),
+        SettlePosition,
+    ]
+);
+variant_params!(
+    OrderFlags,
+    [
+        OrderFlags::BaseCurrency,
+        OrderFlags::QuoteCurrency,
+        OrderFlags::NoMarketPriceProtection,
+        OrderFlags::PostOnly,
+    ]
+);
+variant_params!(
+    EarnLockType,
+    [EarnLockType::Flex, EarnLockType::Bonded, EarnLockType::Timed, EarnLockType::Instant]
+);
+variant_params!(ExportReport, [ExportReport::Trades, ExportReport::Ledgers]);
+variant_params!(
+    asset::AssetPairInfo,
+    [
+        asset::AssetPairInfo::Info,
+        asset::AssetPairInfo::Leverage,
+        asset::AssetPairInfo::Fees,
+        asset::AssetPairInfo::Margin,
+    ]
+);
+
+/// Whether an endpoint is part of Kraken's public or private API. Requests to private endpoints
+/// are signed with the client's credentials
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MethodType {
     Private,
     Public,
 }
@@ -157,7 +342,10 @@ impl fmt::Display for LedgerType {
     }
 }
 
-/// Order trade type | See [KIAddOrder][private::add_order::KIAddOrder]
+/// Order trade type | See [KIAddOrder][private::add_order::KIAddOrder] -
+/// [KOOrderDescription][private::KOOrderDescription]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[serde(rename_all = "lowercase")]
 pub enum TradeType {
     Buy,
     Sell,
@@ -200,8 +388,8 @@ use OrderType::{
     Limit, Market, SettlePosition, StopLoss, StopLossLimit, TakeProfit, TakeProfitLimit,
 };
 impl OrderType {
-    // FIXME: Avoid the empty strings using options and fix the pattern matching in
-    // percent_encode()
+    // FIXME: Avoid the empty strings using options and fix the pattern matching in price1() and
+    // price2()
     fn elide(&self) -> (Option<String>, Option<String>) {
         match self {
             Market => (None, None),
@@ -214,45 +402,15 @@ impl OrderType {
         }
     }
 
-    pub(crate) fn percent_encode(&self) -> (Option<String>, Option<String>) {
-        match self.elide() {
-            (Some(price1), Some(price2)) => {
-                let encoded_price1 = price1
-                    .replace("+", "%2B")
-                    .replace("#", "%23")
-                    .replace("%", "%25");
-
-                let encoded_price2 = price2
-                    .replace("+", "%2B")
-                    .replace("#", "%23")
-                    .replace("%", "%25");
-
-                (Some(encoded_price1), Some(encoded_price2))
-            }
-            (Some(price1), None) => {
-                let encoded_price1 = price1
-                    .replace("+", "%2B")
-                    .replace("#", "%23")
-                    .replace("%", "%25");
-
-                (Some(encoded_price1), None)
-            }
-            (None, Some(_)) => {
-                unreachable!()
-            }
-            (None, None) => (None, None),
-        }
-    }
-
     pub(crate) fn price1(&self) -> Option<String> {
-        match self.percent_encode() {
+        match self.elide() {
             (Some(price), _) => Some(price),
             (None, _) => None,
         }
     }
 
     pub(crate) fn price2(&self) -> Option<String> {
-        match self.percent_encode() {
+        match self.elide() {
             (_, Some(price)) => Some(price),
             (_, None) => None,
         }
@@ -296,6 +454,49 @@ impl fmt::Display for OrderFlags {
     }
 }
 
+/// Lock up type of an earn strategy | See
+/// [KIEarnStrategies][private::earn_strategies::KIEarnStrategies]
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum EarnLockType {
+    /// Funds can be deallocated at any time
+    Flex,
+    /// Funds go through bonding and unbonding periods
+    Bonded,
+    /// Funds are locked for a fixed amount of time
+    Timed,
+    /// Funds can be deallocated instantly
+    Instant,
+}
+
+impl fmt::Display for EarnLockType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EarnLockType::Flex => write!(f, "flex"),
+            EarnLockType::Bonded => write!(f, "bonded"),
+            EarnLockType::Timed => write!(f, "timed"),
+            EarnLockType::Instant => write!(f, "instant"),
+        }
+    }
+}
+
+/// Type of data to export | See [KIAddExport][private::add_export::KIAddExport]
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportReport {
+    Trades,
+    Ledgers,
+}
+
+impl fmt::Display for ExportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportReport::Trades => write!(f, "trades"),
+            ExportReport::Ledgers => write!(f, "ledgers"),
+        }
+    }
+}
+
 pub(crate) struct EndpointInfo {
     methodtype: MethodType,
     endpoint: String,
@@ -308,19 +509,132 @@ impl EndpointInfo {
     pub(crate) fn endpoint(&self) -> &String {
         &self.endpoint
     }
+    // Endpoints that can move funds off the exchange. The client refuses to send these unless
+    // withdrawals were explicitly enabled when it was constructed
+    pub(crate) fn is_withdrawal(&self) -> bool {
+        matches!(
+            self.endpoint.as_str(),
+            "WithdrawInfo" | "Withdraw" | "WithdrawStatus" | "WithdrawCancel"
+        )
+    }
+    // Private endpoints that only report on the account and never place orders or move funds.
+    // A KrakenClientPool sends these with its query keys and everything else with its trading
+    // keys
+    pub(crate) fn is_query(&self) -> bool {
+        matches!(
+            self.endpoint.as_str(),
+            "Balance"
+                | "TradeBalance"
+                | "OpenOrders"
+                | "ClosedOrders"
+                | "QueryOrders"
+                | "TradesHistory"
+                | "QueryTrades"
+                | "OpenPositions"
+                | "Ledgers"
+                | "QueryLedgers"
+                | "TradeVolume"
+                | "AddExport"
+                | "ExportStatus"
+                | "RetrieveExport"
+                | "RemoveExport"
+                | "Earn/Strategies"
+                | "Earn/Allocations"
+                | "Earn/AllocateStatus"
+                | "Earn/DeallocateStatus"
+        )
+    }
+    // Array parameters of the endpoint. Their builders store the items as a comma separated
+    // list, which is sent as one pair per item. Parameters of any other endpoint, or set through
+    // a KICustom for one, are sent as they are
+    pub(crate) fn list_params(&self) -> &'static [&'static str] {
+        match self.endpoint.as_str() {
+            "Earn/Strategies" => &["lock_type[]"],
+            _ => &[],
+        }
+    }
 }
 
 /// Fully constructed input data to be passed to a [KrakenClient][super::client::KrakenClient]
+///
+/// `O` is the [Output] type Kraken responds with, so the client knows what to parse the response
+/// into and an input can't be paired with the wrong output type
 /// # Note
 /// KrakenInput can't be constructed directly. An instance is created by calling finish() or
 /// finish_clone() on an input builder type (types prefixed with "KI"). See the [Input] trait for
 /// KrakenInput builder types
-pub struct KrakenInput {
+pub struct KrakenInput<O> {
     info: EndpointInfo,
     params: Option<IndexMap<String, String>>,
+    output: PhantomData<fn() -> O>,
 }
 
-impl KrakenInput {
+impl<O> Clone for KrakenInput<O> {
+    fn clone(&self) -> Self {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: self.info.methodtype,
+                endpoint: self.info.endpoint.clone(),
+            },
+            params: self.params.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<O> Debug for KrakenInput<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KrakenInput")
+            .field("endpoint", &self.info.endpoint)
+            .field("method", &self.info.methodtype)
+            .field("params", &self.params.as_ref().map(RedactedParams))
+            .finish()
+    }
+}
+
+/// Serializes as `{"endpoint": ..., "method": ..., "params": {...}}`
+impl<O> Serialize for KrakenInput<O> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut input = serializer.serialize_struct("KrakenInput", 3)?;
+        input.serialize_field("endpoint", &self.info.endpoint)?;
+        input.serialize_field("method", &self.info.methodtype)?;
+        input.serialize_field("params", &self.params)?;
+        input.end()
+    }
+}
+
+impl<O> KrakenInput<O> {
+    /// Name of the endpoint the input is sent to, e.g. `AddOrder`
+    pub fn endpoint(&self) -> &str {
+        &self.info.endpoint
+    }
+
+    /// Whether the endpoint is public or private
+    pub fn method(&self) -> MethodType {
+        self.info.methodtype
+    }
+
+    /// Value of the parameter `key`, as it is sent before form encoding
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .as_ref()
+            .and_then(|params| params.get(key))
+            .map(String::as_str)
+    }
+
+    /// Iterator over the parameters in the order they are sent
+    pub fn param_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
     pub(crate) fn info(&self) -> &EndpointInfo {
         &self.info
     }
@@ -336,15 +650,63 @@ impl KrakenInput {
 /// Trait used by input builder types to construct a [KrakenInput]. All input builder
 /// types implement this trait
 pub trait Input {
-    fn finish(self) -> KrakenInput;
-    fn finish_clone(self) -> (KrakenInput, Self);
+    /// The output type returned by the endpoint this builder constructs input for
+    type Output: Output;
+
+    fn finish(self) -> KrakenInput<Self::Output>;
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self);
+}
+
+/// Anything that can be sent with [send()][super::client::KrakenClient::send]: every input
+/// builder type as well as a finished [KrakenInput]. The output type of the endpoint is known
+/// from the input so it never has to be spelled out, and asking for another one doesn't compile
+///
+/// ```compile_fail
+/// # use kraapi::client::KrakenClient;
+/// # use kraapi::api::asset::{KAsset, KAssetPair};
+/// # use kraapi::public::asset_pairs::KOAssetPairInfo;
+/// # use kraapi::public::ticker::KITicker;
+/// # async fn ticker(client: KrakenClient) {
+/// let info: KOAssetPairInfo = client
+///     .send(KITicker::build(KAssetPair(KAsset::XBT, KAsset::USD)))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub trait Endpoint {
+    /// The output type returned by the endpoint
+    type Output: Output;
+
+    /// Convert into a [KrakenInput], calling [finish()][Input::finish] on builder types
+    fn into_input(self) -> KrakenInput<Self::Output>;
+}
+
+impl<O: Output> Endpoint for KrakenInput<O> {
+    type Output = O;
+
+    fn into_input(self) -> KrakenInput<O> {
+        self
+    }
+}
+
+impl<I: Input> Endpoint for I {
+    type Output = I::Output;
+
+    fn into_input(self) -> KrakenInput<I::Output> {
+        self.finish()
+    }
 }
 
-/// Marker trait for output types that are returned from Kraken. Ensures that
+/// Marker trait for output types that are returned from Kraken. Every [Input] type names the
+/// output type of its endpoint, which ensures that
 /// [KrakenClient][super::client::KrakenClient]'s [request][super::client::KrakenClient::request]
 /// method expects the correct output types
 pub trait Output {}
 
+/// Untyped json output, for endpoints without a dedicated output type. See
+/// [request_value()][super::client::KrakenClient::request_value]
+impl Output for serde_json::Value {}
+
 // This trait allows us to get a mutable reference to the input data
 pub(crate) trait MutateInput {
     // Get mutable access to the input parameters of the implementing type
@@ -459,26 +821,52 @@ pub(crate) trait UpdateInput: MutateInput {
     }
 }
 
-pub(crate) fn format_params<T, U>(params: &Option<&IndexMap<T, U>>) -> Option<String>
+// Serialize the parameters as an application/x-www-form-urlencoded string. The same string is
+// used as the query, or as the body and in the signature of private requests. The `lists` are
+// the endpoint's array parameters, see EndpointInfo::list_params
+pub(crate) fn format_params<T, U>(
+    params: &Option<&IndexMap<T, U>>,
+    lists: &[&str],
+) -> Option<String>
 where
     T: Display,
     U: Display,
 {
-    match params {
-        Some(params) => {
-            let mut res = String::new();
-            for index in 0..params.len() {
-                let pair = params.get_index(index).unwrap();
-                if index == 0 {
-                    res = format!("{}{}={}", res, pair.0, pair.1);
-                } else {
-                    res = format!("{}&{}={}", res, pair.0, pair.1);
-                }
+    params.map(|params| {
+        params
+            .iter()
+            .flat_map(|(key, value)| {
+                let (key, value) = (key.to_string(), value.to_string());
+                let values = match lists.contains(&key.as_str()) {
+                    true => value
+                        .split(',')
+                        .filter(|item| !item.is_empty())
+                        .map(String::from)
+                        .collect(),
+                    false => vec![value],
+                };
+                values
+                    .into_iter()
+                    .map(move |value| format!("{}={}", form_encode(&key), form_encode(&value)))
+            })
+            .collect::<Vec<String>>()
+            .join("&")
+    })
+}
+
+// Percent encode everything except alphanumerics and `*-._`, with spaces encoded as `+`
+fn form_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                encoded.push(byte as char)
             }
-            Some(res)
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
         }
-        None => None,
     }
+    encoded
 }
 
 #[cfg(test)]
@@ -486,6 +874,7 @@ mod tests {
     use super::*;
     use crate::api::asset::*;
     use std::collections::BTreeMap;
+    use std::time::Duration;
     use crate::private::account_balance::*;
     use crate::private::trade_balance::*;
     use crate::private::open_orders::*;
@@ -501,6 +890,23 @@ mod tests {
     use crate::private::cancel_order::*;
     use crate::private::cancel_all_orders::*;
     use crate::private::cancel_on_timeout::*;
+    use crate::private::withdraw_info::*;
+    use crate::private::withdraw::*;
+    use crate::private::withdraw_status::*;
+    use crate::private::withdraw_cancel::*;
+    use crate::private::wallet_transfer::*;
+    use crate::private::create_subaccount::*;
+    use crate::private::account_transfer::*;
+    use crate::private::earn_strategies::*;
+    use crate::private::earn_allocate::*;
+    use crate::private::earn_deallocate::*;
+    use crate::private::earn_allocate_status::*;
+    use crate::private::earn_deallocate_status::*;
+    use crate::private::earn_allocations::*;
+    use crate::private::add_export::*;
+    use crate::private::export_status::*;
+    use crate::private::retrieve_export::*;
+    use crate::private::remove_export::*;
 
     use crate::public::server_time::*;
     use crate::public::system_status::*;
@@ -512,57 +918,288 @@ mod tests {
     use crate::public::recent_trades::*;
     use crate::public::spread_data::*;
 
+    // Drop the output type so inputs for different endpoints can be collected together
+    fn info<O>(input: KrakenInput<O>) -> EndpointInfo {
+        input.info
+    }
+
     #[test]
     fn private_apis() {
         let apis = BTreeMap::from([
-            ("Balance", KIAccountBalance::build()),
-            ("AddOrder", KIAddOrder::build(
+            ("Balance", info(KIAccountBalance::build())),
+            ("AddOrder", info(KIAddOrder::build(
                  KAssetPair(KAsset::XBT, KAsset::USD),
                  TradeType::Buy,
                  OrderType::Limit(String::from("101.9901")),
-                 2.12345678).finish()),
-            ("CancelAll", KICancelAllOrders::build()),
-            ("CancelAllOrdersAfter", KICancelOnTimeout::build(10).finish()),
-            ("CancelOrder", KICancelOrder::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish()),
-            ("ClosedOrders", KIClosedOrders::build().finish()),
-            ("Ledgers", KILedgerInfo::build().finish()),
-            ("OpenOrders", KIOpenOrders::build().finish()),
-            ("OpenPositions", KIOpenPositions::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish()),
-            ("QueryLedgers", KIQueryLedgers::build(String::from("L4UESK-KG3EQ-UFO4T5")).finish()),
-            ("QueryOrders", KIQueryOrders::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish()),
-            ("QueryTrades", KITradesInfo::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish()),
-            ("TradeBalance", KITradeBalance::build().finish()),
-            ("TradesHistory", KITradeHistory::build().finish()),
-            ("TradeVolume", KITradeVolume::build().finish())
+                 2.12345678).finish())),
+            ("CancelAll", info(KICancelAllOrders::build())),
+            ("CancelAllOrdersAfter", info(KICancelOnTimeout::build(Duration::from_secs(10)).finish())),
+            ("CancelOrder", info(KICancelOrder::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish())),
+            ("ClosedOrders", info(KIClosedOrders::build().finish())),
+            ("Ledgers", info(KILedgerInfo::build().finish())),
+            ("OpenOrders", info(KIOpenOrders::build().finish())),
+            ("OpenPositions", info(KIOpenPositions::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish())),
+            ("QueryLedgers", info(KIQueryLedgers::build(String::from("L4UESK-KG3EQ-UFO4T5")).finish())),
+            ("QueryOrders", info(KIQueryOrders::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish())),
+            ("QueryTrades", info(KITradesInfo::build(String::from("OYVGEW-VYV5B-UUEXSK")).finish())),
+            ("TradeBalance", info(KITradeBalance::build().finish())),
+            ("TradesHistory", info(KITradeHistory::build().finish())),
+            ("TradeVolume", info(KITradeVolume::build().finish())),
+            ("WithdrawInfo", info(KIWithdrawInfo::build(KAsset::XBT, String::from("btc_cold"), 0.5).finish())),
+            ("Withdraw", info(KIWithdraw::build(KAsset::XBT, String::from("btc_cold"), 0.5).finish())),
+            ("WithdrawStatus", info(KIWithdrawStatus::build(KAsset::XBT).finish())),
+            ("WithdrawCancel", info(KIWithdrawCancel::build(KAsset::XBT, String::from("AGBSO6T-UFMTTQ-I7KGS6")).finish())),
+            ("WalletTransfer", info(KIWalletTransfer::build(KAsset::XBT, 0.5).finish())),
+            ("CreateSubaccount", info(KICreateSubaccount::build(String::from("strategy1"), String::from("ops@example.com")).finish())),
+            ("AccountTransfer", info(KIAccountTransfer::build(KAsset::XBT, 0.5,
+                String::from("ABCD1234EFGH5678"), String::from("IJKL0987MNOP6543")).finish())),
+            ("Earn/Strategies", info(KIEarnStrategies::build().finish())),
+            ("Earn/Allocate", info(KIEarnAllocate::build(String::from("ESRFUO3-Q62XD-WIOIL7"), 10.0).finish())),
+            ("Earn/Deallocate", info(KIEarnDeallocate::build(String::from("ESRFUO3-Q62XD-WIOIL7"), 10.0).finish())),
+            ("Earn/AllocateStatus", info(KIEarnAllocateStatus::build(String::from("ESRFUO3-Q62XD-WIOIL7")).finish())),
+            ("Earn/DeallocateStatus", info(KIEarnDeallocateStatus::build(String::from("ESRFUO3-Q62XD-WIOIL7")).finish())),
+            ("Earn/Allocations", info(KIEarnAllocations::build().finish())),
+            ("AddExport", info(KIAddExport::build(ExportReport::Trades, String::from("yearly")).finish())),
+            ("ExportStatus", info(KIExportStatus::build(ExportReport::Trades).finish())),
+            ("RetrieveExport", info(KIRetrieveExport::build(String::from("TCJA")).finish())),
+            ("RemoveExport", info(KIRemoveExport::build(String::from("TCJA"), RemoveExportType::Delete).finish()))
         ]);
 
         for (key, value) in apis.iter() {
-            assert_eq!(key, value.info().endpoint(), 
-                       "Endpoint: Expected {}, Found {}\n", key, value.info().endpoint());
-            assert_eq!(&MethodType::Private, value.info().method(),
+            assert_eq!(key, value.endpoint(), 
+                       "Endpoint: Expected {}, Found {}\n", key, value.endpoint());
+            assert_eq!(&MethodType::Private, value.method(),
                        "{} should be a private endpoint\n", key);
+            assert_eq!(key.starts_with("Withdraw"), value.is_withdrawal(),
+                       "{} has the wrong withdrawal classification\n", key);
         }
     }
 
     #[test]
     fn public_apis() {
         let apis = BTreeMap::from([
-            ("Time", KIServerTime::build()),
-            ("SystemStatus", KISystemStatus::build()),
-            ("Assets", KIAssetInfo::build().finish()),
-            ("AssetPairs", KIAssetPairs::build().finish()),
-            ("Ticker", KITicker::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish()),
-            ("OHLC", KIOHLC::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish()),
-            ("Depth", KIOrderBook::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish()),
-            ("Trades", KIRecentTrades::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish()),
-            ("Spread", KISpreadData::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish()),
+            ("Time", info(KIServerTime::build())),
+            ("SystemStatus", info(KISystemStatus::build())),
+            ("Assets", info(KIAssetInfo::build().finish())),
+            ("AssetPairs", info(KIAssetPairs::build().finish())),
+            ("Ticker", info(KITicker::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish())),
+            ("OHLC", info(KIOHLC::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish())),
+            ("Depth", info(KIOrderBook::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish())),
+            ("Trades", info(KIRecentTrades::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish())),
+            ("Spread", info(KISpreadData::build(KAssetPair(KAsset::XBT, KAsset::USD)).finish())),
         ]);
 
         for (key, value) in apis.iter() {
-            assert_eq!(key, value.info().endpoint(), 
-                       "Endpoint: Expected {}, Found {}\n", key, value.info().endpoint());
-            assert_eq!(&MethodType::Public, value.info().method(),
+            assert_eq!(key, value.endpoint(), 
+                       "Endpoint: Expected {}, Found {}\n", key, value.endpoint());
+            assert_eq!(&MethodType::Public, value.method(),
                        "{} should be a public endpoint\n", key);
         }
     }
+
+    #[test]
+    fn form_encoding() {
+        let input = KIAddOrder::build(
+            KAssetPair(KAsset::XBT, KAsset::USD),
+            TradeType::Buy,
+            OrderType::StopLossLimit(String::from("+5%"), String::from("#10")),
+            1.5,
+        )
+        .with_closing_order(OrderType::TakeProfit(String::from("#5%")))
+        .start_in(Duration::from_secs(30))
+        .expire_in(Duration::from_secs(90))
+        .finish();
+        // Both delays are relative to when the order is placed, so the order is live for a minute
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "pair=XBTUSD&type=buy&ordertype=stop-loss-limit&price=%2B5%25&price2=%2310&\
+             volume=1.5&close%5Bordertype%5D=take-profit&close%5Bprice%5D=%235%25&starttm=%2B30&\
+             expiretm=%2B90"
+        );
+
+        let input = KIWalletTransfer::build(KAsset::XBT, 0.5).finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "asset=XBT&from=Spot+Wallet&to=Futures+Wallet&amount=0.5"
+        );
+
+        let input = KIEarnStrategies::build()
+            .with_lock_type(EarnLockType::Flex)
+            .with_lock_type_list([EarnLockType::Bonded, EarnLockType::Flex])
+            .finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "lock_type%5B%5D=flex&lock_type%5B%5D=bonded"
+        );
+        let input = KIEarnStrategies::build()
+            .with_lock_type(EarnLockType::Flex)
+            .clear_lock_type_list()
+            .finish();
+        assert_eq!(format_params(&input.params(), input.info().list_params()).unwrap(), "");
+
+        let params = IndexMap::from([("description", "Q1 & Q2 = 50/50~")]);
+        assert_eq!(
+            format_params(&Some(&params), &[]).unwrap(),
+            "description=Q1+%26+Q2+%3D+50%2F50%7E"
+        );
+    }
+
+    #[test]
+    fn serialize_builders() {
+        let template = KIAddOrder::build(
+            KAssetPair(KAsset::XBT, KAsset::USD),
+            TradeType::Buy,
+            OrderType::Limit(String::from("101.9901")),
+            2.12345678,
+        )
+        .with_leverage(Leverage::Two)
+        .with_order_flags(vec![OrderFlags::PostOnly, OrderFlags::QuoteCurrency])
+        .with_closing_order(OrderType::StopLoss(String::from("#5%")));
+
+        let json = serde_json::to_string(&template).unwrap();
+        assert_eq!(
+            json,
+            r##"{"pair":"XBTUSD","type":"buy","ordertype":"limit","price":"101.9901","volume":"2.12345678","leverage":"2:1","oflags":"post,fciq","close[ordertype]":"stop-loss","close[price]":"#5%"}"##
+        );
+
+        let template: KIAddOrder = serde_json::from_str(&json).unwrap();
+        let input = template.validate(true).finish();
+        assert_eq!(input.endpoint(), "AddOrder");
+        assert_eq!(input.method(), MethodType::Private);
+        assert_eq!(input.param("close[price]"), Some("#5%"));
+        assert_eq!(input.param_iter().last(), Some(("validate", "true")));
+        assert_eq!(
+            serde_json::to_value(&input).unwrap()["params"]["oflags"],
+            "post,fciq"
+        );
+
+        // A misspelled parameter is rejected rather than sent to Kraken
+        let err = serde_json::from_str::<KIAddOrder>(r#"{"pair":"XBTUSD","levrage":"2:1"}"#)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("unknown field `levrage`"));
+
+        // So is a template missing a parameter build() requires
+        let err = serde_json::from_str::<KIAddOrder>(
+            r#"{"type":"buy","ordertype":"market","volume":"1.5"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("missing field `pair`"));
+        let err = serde_json::from_str::<KIAddOrder>(
+            r#"{"pair":"XBTUSD","type":"buy","ordertype":"market","volume":""}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("missing field `volume`"));
+
+        // Or one holding a value no setter produces
+        let order = r#"{"pair":"XBTUSD","type":"buy","volume":"1.5","ordertype":"#;
+        for invalid in [
+            r#""limitt"}"#,
+            r#""limit","oflags":"post,fcix"}"#,
+            r#""limit","leverage":"6:1"}"#,
+            r#""limit","starttm":"+-5"}"#,
+            r#""limit","userref":"-1"}"#,
+        ] {
+            let err = serde_json::from_str::<KIAddOrder>(&format!("{}{}", order, invalid))
+                .unwrap_err();
+            assert!(err.to_string().starts_with("invalid value"), "{}", err);
+        }
+        let err = serde_json::from_str::<KILedgerInfo>(r#"{"asset":"XXBT,NOTANASSET"}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("a valid value for asset"));
+
+        // Every setter's output deserializes back
+        let order = KIAddOrder::build(
+            KAssetPair(KAsset::ETH, KAsset::EUR),
+            TradeType::Sell,
+            OrderType::StopLossLimit(String::from("+5"), String::from("-2%")),
+            0.5,
+        )
+        .with_leverage(Leverage::Five)
+        .start_in(Duration::from_secs(30))
+        .expire_at(1_688_669_597u64)
+        .with_userref(u32::MAX)
+        .validate(false);
+        let json = serde_json::to_string(&order).unwrap();
+        let order: KIAddOrder = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&order).unwrap(), json);
+
+        let ledgers = KILedgerInfo::build()
+            .with_asset_list(vec![KAsset::XBT, KAsset::USD])
+            .with_trade_type(LedgerType::Withdrawal)
+            .starting_timestamp(1_688_669_597u64)
+            .ending_legid(String::from("L4UESK-KG3EQ-UFO4T5"))
+            .with_offset(50);
+        let json = serde_json::to_string(&ledgers).unwrap();
+        let ledgers: KILedgerInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&ledgers).unwrap(), json);
+
+        let ticker = KITicker::build_with_list(vec![
+            KAssetPair(KAsset::XBT, KAsset::USD),
+            KAssetPair(KAsset::ETH, KAsset::XBT),
+        ]);
+        let json = serde_json::to_string(&ticker).unwrap();
+        let ticker: KITicker = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&ticker).unwrap(), json);
+
+        let (input, _) = serde_json::from_str::<KIServerTime>("[]").unwrap().finish_clone();
+        assert_eq!(input.endpoint(), "Time");
+    }
+
+    #[test]
+    fn serialize_earn_builders() {
+        let strategy = String::from("ESRFUO3-Q62XD-WIOIL7");
+
+        let strategies = KIEarnStrategies::build()
+            .with_asset(KAsset::DOT)
+            .with_lock_type_list([EarnLockType::Flex, EarnLockType::Bonded])
+            .ascending(true)
+            .with_limit(10);
+        let json = serde_json::to_string(&strategies).unwrap();
+        assert_eq!(
+            json,
+            r#"{"asset":"DOT","lock_type[]":"flex,bonded","ascending":"true","limit":"10"}"#
+        );
+        let input = serde_json::from_str::<KIEarnStrategies>(&json).unwrap().finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "asset=DOT&lock_type%5B%5D=flex&lock_type%5B%5D=bonded&ascending=true&limit=10"
+        );
+
+        let allocate = KIEarnAllocate::build(strategy.clone(), 10.5);
+        let json = serde_json::to_string(&allocate).unwrap();
+        assert_eq!(json, r#"{"strategy_id":"ESRFUO3-Q62XD-WIOIL7","amount":"10.5"}"#);
+        let input = serde_json::from_str::<KIEarnAllocate>(&json).unwrap().finish();
+        assert_eq!(input.endpoint(), "Earn/Allocate");
+        let deallocate = KIEarnDeallocate::build(strategy.clone(), 10.5);
+        assert_eq!(serde_json::to_string(&deallocate).unwrap(), json);
+        let input = serde_json::from_str::<KIEarnDeallocate>(&json).unwrap().finish();
+        assert_eq!(input.endpoint(), "Earn/Deallocate");
+        let json = r#"{"strategy_id":"ESRFUO3-Q62XD-WIOIL7"}"#;
+        let err = serde_json::from_str::<KIEarnDeallocate>(json).unwrap_err();
+        assert!(err.to_string().starts_with("missing field `amount`"));
+
+        let status = KIEarnAllocateStatus::build(strategy.clone());
+        assert_eq!(serde_json::to_string(&status).unwrap(), json);
+        let status = serde_json::from_str::<KIEarnAllocateStatus>(&json).unwrap().finish();
+        assert_eq!(status.param("strategy_id"), Some(strategy.as_str()));
+        let status = KIEarnDeallocateStatus::build(strategy);
+        assert_eq!(serde_json::to_string(&status).unwrap(), json);
+        let status = serde_json::from_str::<KIEarnDeallocateStatus>(&json).unwrap().finish();
+        assert_eq!(status.endpoint(), "Earn/DeallocateStatus");
+
+        let allocations = KIEarnAllocations::build()
+            .ascending(false)
+            .with_converted_asset(KAsset::USD)
+            .hide_zero_allocations(true);
+        let json = serde_json::to_string(&allocations).unwrap();
+        assert_eq!(
+            json,
+            r#"{"ascending":"false","converted_asset":"USD","hide_zero_allocations":"true"}"#
+        );
+        let input = serde_json::from_str::<KIEarnAllocations>(&json).unwrap().finish();
+        assert_eq!(
+            format_params(&input.params(), input.info().list_params()).unwrap(),
+            "ascending=false&converted_asset=USD&hide_zero_allocations=true"
+        );
+    }
 }
@@ -1,8 +1,8 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 use crate::api::asset::KAsset;
@@ -11,47 +11,50 @@ use crate::api::asset::KAsset;
 use super::{Input, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Get Account Balance endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIAccountBalance {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIAccountBalance, []);
+
 impl KIAccountBalance {
     /// Constructor returning a [KrakenInput] builder for the get trade balance endpoint.
     /// There are no inputs to this endpoint so finish() is called for you
-    pub fn build() -> KrakenInput {
+    pub fn build() -> KrakenInput<KOAccountBalance> {
         let account_balance = KIAccountBalance {
             params: IndexMap::new(),
         };
         account_balance.finish()
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIAccountBalance {
-    fn finish(self) -> KrakenInput {
+    type Output = KOAccountBalance;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("Balance"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("Balance"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
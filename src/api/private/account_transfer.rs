@@ -0,0 +1,126 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Account Transfer endpoint
+///
+/// Transfers funds between a master account and its subaccounts. Only available to
+/// institutional clients
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIAccountTransfer {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIAccountTransfer,
+    [
+        "asset" => KAsset,
+        "amount" => f64,
+        "from" => String,
+        "to" => String,
+    ],
+    required = ["asset", "amount", "from", "to"]
+);
+
+impl KIAccountTransfer {
+    /// Constructor returning a [KrakenInput] builder for the account transfer endpoint.
+    /// * `asset` - asset being transferred
+    /// * `amount` - amount of `asset` to transfer
+    /// * `from` - IIBAN of the source account
+    /// * `to` - IIBAN of the destination account
+    pub fn build(asset: KAsset, amount: f64, from: String, to: String) -> Self {
+        let account_transfer = KIAccountTransfer {
+            params: IndexMap::new(),
+        };
+        account_transfer
+            .with_asset(asset)
+            .with_amount(amount)
+            .from_account(from)
+            .to_account(to)
+    }
+
+    /// Update the asset being transferred. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Update the amount to transfer. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+
+    /// Update the IIBAN of the source account. Useful for templating
+    pub fn from_account(self, iiban: String) -> Self {
+        self.update_input("from", iiban)
+    }
+
+    /// Update the IIBAN of the destination account. Useful for templating
+    pub fn to_account(self, iiban: String) -> Self {
+        self.update_input("to", iiban)
+    }
+}
+
+impl MutateInput for KIAccountTransfer {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIAccountTransfer {}
+
+impl Input for KIAccountTransfer {
+    type Output = KOAccountTransfer;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("AccountTransfer"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("AccountTransfer"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Status of an account transfer | See [KOAccountTransfer]
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum KOTransferStatus {
+    /// Transfer is being processed
+    Pending,
+    /// Transfer has completed
+    Complete,
+}
+
+/// Response from the Account Transfer endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOAccountTransfer {
+    /// Transfer ID
+    pub transfer_id: String,
+    /// Transfer status
+    pub status: KOTransferStatus,
+}
+
+impl Output for KOAccountTransfer {}
@@ -0,0 +1,142 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, ExportReport, KrakenInput, MethodType};
+use crate::api::timestamp::KTimestamp;
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// File format of an export report
+pub enum ExportFormat {
+    /// Comma separated values
+    CSV,
+    /// Tab separated values
+    TSV,
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::CSV => write!(f, "CSV"),
+            ExportFormat::TSV => write!(f, "TSV"),
+        }
+    }
+}
+
+variant_params!(ExportFormat, [ExportFormat::CSV, ExportFormat::TSV]);
+
+/// Request builder for the Request Export Report endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIAddExport {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIAddExport,
+    [
+        "report" => ExportReport,
+        "description" => String,
+        "format" => ExportFormat,
+        "fields" => String,
+        "starttm" => KTimestamp,
+        "endtm" => KTimestamp,
+    ],
+    required = ["report", "description"]
+);
+
+impl KIAddExport {
+    /// Constructor returning a [KrakenInput] builder for the request export report endpoint.
+    /// * `report` - type of data to export
+    /// * `description` - description of the export
+    pub fn build(report: ExportReport, description: String) -> Self {
+        let add_export = KIAddExport {
+            params: IndexMap::new(),
+        };
+        add_export.with_report(report).with_description(description)
+    }
+
+    /// Update the type of data to export. Useful for templating
+    pub fn with_report(self, report: ExportReport) -> Self {
+        self.update_input("report", report.to_string())
+    }
+
+    /// Update the description of the export. Useful for templating
+    pub fn with_description(self, description: String) -> Self {
+        self.update_input("description", description)
+    }
+
+    /// File format of the export. Kraken defaults to CSV
+    pub fn with_format(self, format: ExportFormat) -> Self {
+        self.update_input("format", format.to_string())
+    }
+
+    /// Comma delimited list of fields to include in the export. Kraken defaults to all fields.
+    /// The txid field must be included for the export to be parsed by
+    /// [KORetrieveExport][super::retrieve_export::KORetrieveExport]
+    pub fn with_fields(self, fields: String) -> Self {
+        self.update_input("fields", fields)
+    }
+
+    /// Starting time of the data to export, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Inclusive
+    pub fn starting_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("starttm", timestamp.into().to_param())
+    }
+
+    /// Ending time of the data to export, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Exclusive
+    pub fn ending_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("endtm", timestamp.into().to_param())
+    }
+}
+
+impl MutateInput for KIAddExport {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIAddExport {}
+
+impl Input for KIAddExport {
+    type Output = KOAddExport;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("AddExport"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("AddExport"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Request Export Report endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOAddExport {
+    /// Report ID used to query the status of, retrieve or remove the export
+    pub id: String,
+}
+
+impl Output for KOAddExport {}
@@ -1,13 +1,16 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::time::Duration;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KAssetPair, KrakenInput, MethodType, OrderFlags, OrderType, TradeType};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, MutateInput, Output, UpdateInput};
+use crate::api::ParamValue;
 
 /// Amount of leverage to be used when placing an order
 pub enum Leverage {
@@ -32,11 +35,60 @@ impl Display for Leverage {
     }
 }
 
+// Leverage is sent as a ratio to 1
+impl ParamValue for Leverage {
+    fn is_valid(value: &str) -> bool {
+        match value.strip_suffix(":1") {
+            Some(leverage) => [Leverage::Two, Leverage::Three, Leverage::Four, Leverage::Five]
+                .iter()
+                .any(|variant| variant.to_string() == leverage),
+            None => false,
+        }
+    }
+}
+
+// Order start and expiry times, either a delay in seconds from now prefixed with + or a Unix
+// timestamp
+struct OrderTime;
+
+impl ParamValue for OrderTime {
+    fn is_valid(value: &str) -> bool {
+        match value.strip_prefix('+') {
+            Some(delay) => delay.parse::<u64>().is_ok(),
+            None => KTimestamp::is_valid(value),
+        }
+    }
+}
+
 /// Request builder for the Add Standard Order endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIAddOrder {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KIAddOrder,
+    [
+        "pair" => KAssetPair,
+        "type" => TradeType,
+        "ordertype" => OrderType,
+        "price" => String,
+        "price2" => String,
+        "volume" => f64,
+        "leverage" => Leverage,
+        "oflags" => [OrderFlags],
+        "starttm" => OrderTime,
+        "expiretm" => OrderTime,
+        "userref" => u32,
+        "validate" => bool,
+        "close[ordertype]" => OrderType,
+        "close[price]" => String,
+        "close[price2]" => String,
+    ],
+    required = ["pair", "type", "ordertype", "volume"]
+);
+
 impl KIAddOrder {
     /// Constructor returning a KrakenInput builder for the add standard order endpoint
     ///
@@ -131,24 +183,27 @@ impl KIAddOrder {
         }
     }
 
-    /// Scedule the order start time for `secs` seconds from now
-    pub fn start_in(self, secs: u32) -> Self {
-        self.update_input("starttm", String::from("%2B") + &secs.to_string())
+    /// Scedule the order start time for `delay` from now, in whole seconds
+    pub fn start_in(self, delay: Duration) -> Self {
+        self.update_input("starttm", format!("+{}", delay.as_secs()))
     }
 
-    /// Scedule the order start time for the Unix `timestamp` in seconds
-    pub fn start_at(self, timestamp: u64) -> Self {
-        self.update_input("starttm", timestamp.to_string())
+    /// Scedule the order start time for `time`, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]
+    pub fn start_at<T: Into<KTimestamp>>(self, time: T) -> Self {
+        self.update_input("starttm", time.into().to_param())
     }
 
-    /// Order to expire in `secs` seconds
-    pub fn expire_in(self, secs: u32) -> Self {
-        self.update_input("expiretm", secs.to_string())
+    /// Order to expire `delay` from now, in whole seconds. Like [start_in()][Self::start_in] the
+    /// delay counts from when Kraken receives the order, not from its start time
+    pub fn expire_in(self, delay: Duration) -> Self {
+        self.update_input("expiretm", format!("+{}", delay.as_secs()))
     }
 
-    /// Order to expire at the Unix `timestamp` in seconds
-    pub fn expire_at(self, timestamp: u64) -> Self {
-        self.update_input("expiretm", timestamp.to_string())
+    /// Order to expire at `time`, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]
+    pub fn expire_at<T: Into<KTimestamp>>(self, time: T) -> Self {
+        self.update_input("expiretm", time.into().to_param())
     }
 
     /// User supplied unsigned 32 bit integer which Kraken will use to demarcate this order for
@@ -168,12 +223,12 @@ impl KIAddOrder {
         let price2 = ordertype.price2();
         match (price1, price2) {
             (Some(price1), Some(price2)) => self
-                .update_input("close%5Bordertype%5D", ordertype.to_string())
-                .update_input("close%5Bprice%5D", price1)
-                .update_input("close%5Bprice2%5D", price2),
+                .update_input("close[ordertype]", ordertype.to_string())
+                .update_input("close[price]", price1)
+                .update_input("close[price2]", price2),
             (Some(price1), None) => self
-                .update_input("close%5Bordertype%5D", ordertype.to_string())
-                .update_input("close%5Bprice%5D", price1),
+                .update_input("close[ordertype]", ordertype.to_string())
+                .update_input("close[price]", price1),
             (None, Some(_)) => {
                 unreachable!()
             }
@@ -181,10 +236,6 @@ impl KIAddOrder {
         }
     }
 
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
-
     fn format_flag(&mut self, flag: OrderFlags) {
         let listname = String::from("oflags");
         match self.params.get_mut(&listname) {
@@ -211,27 +262,30 @@ impl MutateInput for KIAddOrder {
 impl UpdateInput for KIAddOrder {}
 
 impl Input for KIAddOrder {
-    fn finish(self) -> KrakenInput {
+    type Output = KOAddOrder;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("AddOrder"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("AddOrder"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
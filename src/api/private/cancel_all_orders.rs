@@ -1,7 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -9,47 +9,50 @@ use super::{EndpointInfo, KrakenInput, MethodType};
 use super::{Input, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Cancel All Open Orders endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KICancelAllOrders {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KICancelAllOrders, []);
+
 impl KICancelAllOrders {
     /// Construct and build a [KrakenInput] for the cancel all orders endpoint. No parameters are
     /// needed
-    pub fn build() -> KrakenInput {
+    pub fn build() -> KrakenInput<KOCancelAllOrders> {
         let cancelorders = KICancelAllOrders {
             params: IndexMap::new(),
         };
         cancelorders.finish()
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KICancelAllOrders {
-    fn finish(self) -> KrakenInput {
+    type Output = KOCancelAllOrders;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("CancelAll"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("CancelAll"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -1,22 +1,28 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::time::Duration;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Cancel All Orders After endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KICancelOnTimeout {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KICancelOnTimeout, ["timeout" => u64], required = ["timeout"]);
+
 impl KICancelOnTimeout {
     /// Constructor returning a [KrakenInput] builder for the cancel all orders after... endpoint.
-    /// Cancel all orders in `timeout` seconds
-    pub fn build(timeout: u32) -> KICancelOnTimeout {
+    /// Cancel all orders in `timeout`, in whole seconds
+    pub fn build(timeout: Duration) -> KICancelOnTimeout {
         let cancelorder = KICancelOnTimeout {
             params: IndexMap::new(),
         };
@@ -24,12 +30,8 @@ impl KICancelOnTimeout {
     }
 
     /// Update the timeout value. Useful for templating
-    pub fn on_timeout(self, timeout: u32) -> Self {
-        self.update_input("timeout", timeout.to_string())
-    }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
+    pub fn on_timeout(self, timeout: Duration) -> Self {
+        self.update_input("timeout", timeout.as_secs())
     }
 }
 
@@ -42,27 +44,30 @@ impl MutateInput for KICancelOnTimeout {
 impl UpdateInput for KICancelOnTimeout {}
 
 impl Input for KICancelOnTimeout {
-    fn finish(self) -> KrakenInput {
+    type Output = KOCancelOnTimeout;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("CancelAllOrdersAfter"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("CancelAllOrdersAfter"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -72,11 +77,11 @@ impl Input for KICancelOnTimeout {
 pub struct KOCancelOnTimeout {
     /// Timestamp (RFC3339) reflecting when the request has been handled (second precision, rounded up)
     #[serde(rename = "currentTime")]
-    pub current_time: String,
+    pub current_time: KTimestamp,
     /// Timestamp (RFC3339) reflecting the time at which all open orders will be cancelled,
     /// unless the timer is extended or disabled (second precision, rounded up)
     #[serde(rename = "triggerTime")]
-    pub trigger_time: String,
+    pub trigger_time: KTimestamp,
 }
 
 impl Output for KOCancelOnTimeout {}
@@ -1,7 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -9,10 +9,14 @@ use super::{EndpointInfo, KrakenInput, MethodType};
 use super::{Input, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Cancel Open Order endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KICancelOrder {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KICancelOrder, ["txid" => String], required = ["txid"]);
+
 impl KICancelOrder {
     /// Constructor returning a [KrakenInput] builder for the cancel open order endpoint.
     /// txid is the transaction ID of the order that is to be cancelled
@@ -28,10 +32,6 @@ impl KICancelOrder {
     pub fn with_txid(self, txid: String) -> Self {
         self.update_input("txid", txid)
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl MutateInput for KICancelOrder {
@@ -43,27 +43,30 @@ impl MutateInput for KICancelOrder {
 impl UpdateInput for KICancelOrder {}
 
 impl Input for KICancelOrder {
-    fn finish(self) -> KrakenInput {
+    type Output = KOCancelOrder;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("CancelOrder"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("CancelOrder"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -1,10 +1,11 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType, OrderCloseTime};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, MutateInput, Output, UpdateInput};
@@ -14,10 +15,24 @@ pub use super::KOOrderInfo;
 pub use super::KOOrderStatus;
 
 /// Request builder for the Get Closed Orders endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIClosedOrders {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KIClosedOrders,
+    [
+        "trades" => bool,
+        "userref" => u32,
+        "start" => String,
+        "end" => String,
+        "ofs" => u64,
+        "closetime" => OrderCloseTime,
+    ]
+);
+
 impl KIClosedOrders {
     /// Constructor returning a [KrakenInput] builder for the get closed orders endpoint.
     pub fn build() -> Self {
@@ -41,14 +56,16 @@ impl KIClosedOrders {
         self.update_input("userref", userref.to_string())
     }
 
-    /// Starting Unix timestamp to filter output by. Exclusive
-    pub fn starting_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("start", timestamp.to_string())
+    /// Starting time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Exclusive
+    pub fn starting_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("start", timestamp.into().to_param())
     }
 
-    /// Ending Unix timestamp to filter output by. Inclusive
-    pub fn ending_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("end", timestamp.to_string())
+    /// Ending time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Inclusive
+    pub fn ending_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("end", timestamp.into().to_param())
     }
 
     /// Starting transaction ID to filter output by. Exclusive
@@ -70,34 +87,33 @@ impl KIClosedOrders {
     pub fn with_closetime(self, closetime: OrderCloseTime) -> Self {
         self.update_input("closetime", closetime.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIClosedOrders {
-    fn finish(self) -> KrakenInput {
+    type Output = KOClosedOrders;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("ClosedOrders"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("ClosedOrders"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -0,0 +1,96 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Create Subaccount endpoint
+///
+/// Only available to institutional clients
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KICreateSubaccount {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KICreateSubaccount,
+    [
+        "username" => String,
+        "email" => String,
+    ],
+    required = ["username", "email"]
+);
+
+impl KICreateSubaccount {
+    /// Constructor returning a [KrakenInput] builder for the create subaccount endpoint.
+    /// * `username` - username for the subaccount
+    /// * `email` - email address for the subaccount
+    pub fn build(username: String, email: String) -> Self {
+        let create_subaccount = KICreateSubaccount {
+            params: IndexMap::new(),
+        };
+        create_subaccount.with_username(username).with_email(email)
+    }
+
+    /// Update the username for the subaccount. Useful for templating
+    pub fn with_username(self, username: String) -> Self {
+        self.update_input("username", username)
+    }
+
+    /// Update the email address for the subaccount. Useful for templating
+    pub fn with_email(self, email: String) -> Self {
+        self.update_input("email", email)
+    }
+}
+
+impl MutateInput for KICreateSubaccount {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KICreateSubaccount {}
+
+impl Input for KICreateSubaccount {
+    type Output = KOCreateSubaccount;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("CreateSubaccount"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("CreateSubaccount"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Create Subaccount endpoint
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOCreateSubaccount {
+    /// Whether the subaccount was created
+    pub created: bool,
+}
+
+impl Output for KOCreateSubaccount {}
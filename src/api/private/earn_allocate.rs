@@ -0,0 +1,98 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Allocate Earn Funds endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnAllocate {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIEarnAllocate,
+    [
+        "strategy_id" => String,
+        "amount" => f64,
+    ],
+    required = ["strategy_id", "amount"]
+);
+
+impl KIEarnAllocate {
+    /// Constructor returning a [KrakenInput] builder for the allocate earn funds endpoint.
+    /// * `strategy_id` - ID of the earn strategy
+    /// * `amount` - amount of the strategy's asset to allocate
+    pub fn build(strategy_id: String, amount: f64) -> Self {
+        let allocate = KIEarnAllocate {
+            params: IndexMap::new(),
+        };
+        allocate.with_strategy_id(strategy_id).with_amount(amount)
+    }
+
+    /// Update the earn strategy ID. Useful for templating
+    pub fn with_strategy_id(self, strategy_id: String) -> Self {
+        self.update_input("strategy_id", strategy_id)
+    }
+
+    /// Update the amount to allocate. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+}
+
+impl MutateInput for KIEarnAllocate {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIEarnAllocate {}
+
+impl Input for KIEarnAllocate {
+    type Output = KOEarnAllocate;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/Allocate"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/Allocate"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Allocate Earn Funds endpoint
+///
+/// The allocation is processed asynchronously. Poll
+/// [KIEarnAllocateStatus][super::earn_allocate_status::KIEarnAllocateStatus] to find out when it
+/// has completed
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOEarnAllocate {
+    /// Whether the allocation request was accepted
+    pub accepted: bool,
+}
+
+impl Output for KOEarnAllocate {}
@@ -0,0 +1,73 @@
+use indexmap::map::IndexMap;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, UpdateInput};
+
+pub use super::KOEarnStatus;
+
+/// Request builder for the Get Allocation Status endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnAllocateStatus {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(KIEarnAllocateStatus, ["strategy_id" => String], required = ["strategy_id"]);
+
+impl KIEarnAllocateStatus {
+    /// Constructor returning a [KrakenInput] builder for the get allocation status endpoint.
+    /// * `strategy_id` - ID of the earn strategy
+    pub fn build(strategy_id: String) -> Self {
+        let allocate_status = KIEarnAllocateStatus {
+            params: IndexMap::new(),
+        };
+        allocate_status.with_strategy_id(strategy_id)
+    }
+
+    /// Update the earn strategy ID. Useful for templating
+    pub fn with_strategy_id(self, strategy_id: String) -> Self {
+        self.update_input("strategy_id", strategy_id)
+    }
+}
+
+impl MutateInput for KIEarnAllocateStatus {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIEarnAllocateStatus {}
+
+impl Input for KIEarnAllocateStatus {
+    type Output = KOEarnStatus;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/AllocateStatus"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/AllocateStatus"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
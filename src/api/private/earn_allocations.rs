@@ -0,0 +1,161 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+use crate::api::timestamp::KTimestamp;
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the List Earn Allocations endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnAllocations {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIEarnAllocations,
+    [
+        "ascending" => bool,
+        "converted_asset" => KAsset,
+        "hide_zero_allocations" => bool,
+    ]
+);
+
+impl KIEarnAllocations {
+    /// Constructor returning a [KrakenInput] builder for the list earn allocations endpoint.
+    pub fn build() -> Self {
+        KIEarnAllocations {
+            params: IndexMap::new(),
+        }
+    }
+
+    /// Sort the allocations in ascending order
+    pub fn ascending(self, ascending: bool) -> Self {
+        self.update_input("ascending", ascending.to_string())
+    }
+
+    /// Asset to denominate the converted amounts in. Kraken defaults to USD
+    pub fn with_converted_asset(self, asset: KAsset) -> Self {
+        self.update_input("converted_asset", asset.to_string())
+    }
+
+    /// Omit strategies that currently have nothing allocated to them
+    pub fn hide_zero_allocations(self, hide: bool) -> Self {
+        self.update_input("hide_zero_allocations", hide.to_string())
+    }
+}
+
+impl MutateInput for KIEarnAllocations {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIEarnAllocations {}
+
+impl Input for KIEarnAllocations {
+    type Output = KOEarnAllocations;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/Allocations"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/Allocations"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Amount expressed in both the native asset and the converted asset
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAmount {
+    pub native: String,
+    pub converted: String,
+}
+
+/// Single bonding or unbonding allocation | See [KOEarnAllocationState]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAllocationEntry {
+    pub native: String,
+    pub converted: String,
+    /// Timestamp (RFC3339) of when the allocation was made
+    pub created_at: KTimestamp,
+    /// Timestamp (RFC3339) of when the allocation will leave its current state
+    pub expires: KTimestamp,
+}
+
+/// Funds in a transitional state such as bonding or unbonding | See [KOEarnAllocatedAmount]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAllocationState {
+    pub native: String,
+    pub converted: String,
+    pub allocation_count: Option<u32>,
+    pub allocations: Option<Vec<KOEarnAllocationEntry>>,
+}
+
+/// Breakdown of the funds allocated to a strategy | See [KOEarnAllocation]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAllocatedAmount {
+    pub bonding: Option<KOEarnAllocationState>,
+    pub exit_queue: Option<KOEarnAllocationState>,
+    pub pending: Option<KOEarnAmount>,
+    pub unbonding: Option<KOEarnAllocationState>,
+    pub total: KOEarnAmount,
+}
+
+/// Rewards for the current payout period | See [KOEarnAllocation]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnPayout {
+    pub accumulated_reward: KOEarnAmount,
+    pub estimated_reward: KOEarnAmount,
+    /// Timestamp (RFC3339) of the start of the payout period
+    pub period_start: KTimestamp,
+    /// Timestamp (RFC3339) of the end of the payout period
+    pub period_end: KTimestamp,
+}
+
+/// Allocation to a single earn strategy | See [KOEarnAllocations]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAllocation {
+    pub strategy_id: String,
+    /// Asset allocated to the strategy, as Kraken names it
+    pub native_asset: String,
+    pub amount_allocated: KOEarnAllocatedAmount,
+    pub total_rewarded: KOEarnAmount,
+    pub payout: Option<KOEarnPayout>,
+}
+
+/// Response from the List Earn Allocations endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnAllocations {
+    /// Asset the converted amounts are denominated in, as Kraken names it
+    pub converted_asset: String,
+    /// Total allocated across all strategies in the converted asset
+    pub total_allocated: String,
+    /// Total rewarded across all strategies in the converted asset
+    pub total_rewarded: String,
+    pub next_cursor: Option<String>,
+    pub items: Vec<KOEarnAllocation>,
+}
+
+impl Output for KOEarnAllocations {}
@@ -0,0 +1,98 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Deallocate Earn Funds endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnDeallocate {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIEarnDeallocate,
+    [
+        "strategy_id" => String,
+        "amount" => f64,
+    ],
+    required = ["strategy_id", "amount"]
+);
+
+impl KIEarnDeallocate {
+    /// Constructor returning a [KrakenInput] builder for the deallocate earn funds endpoint.
+    /// * `strategy_id` - ID of the earn strategy
+    /// * `amount` - amount of the strategy's asset to deallocate
+    pub fn build(strategy_id: String, amount: f64) -> Self {
+        let deallocate = KIEarnDeallocate {
+            params: IndexMap::new(),
+        };
+        deallocate.with_strategy_id(strategy_id).with_amount(amount)
+    }
+
+    /// Update the earn strategy ID. Useful for templating
+    pub fn with_strategy_id(self, strategy_id: String) -> Self {
+        self.update_input("strategy_id", strategy_id)
+    }
+
+    /// Update the amount to deallocate. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+}
+
+impl MutateInput for KIEarnDeallocate {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIEarnDeallocate {}
+
+impl Input for KIEarnDeallocate {
+    type Output = KOEarnDeallocate;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/Deallocate"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/Deallocate"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Deallocate Earn Funds endpoint
+///
+/// The deallocation is processed asynchronously. Poll
+/// [KIEarnDeallocateStatus][super::earn_deallocate_status::KIEarnDeallocateStatus] to find out
+/// when it has completed
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOEarnDeallocate {
+    /// Whether the deallocation request was accepted
+    pub accepted: bool,
+}
+
+impl Output for KOEarnDeallocate {}
@@ -0,0 +1,73 @@
+use indexmap::map::IndexMap;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, UpdateInput};
+
+pub use super::KOEarnStatus;
+
+/// Request builder for the Get Deallocation Status endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnDeallocateStatus {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(KIEarnDeallocateStatus, ["strategy_id" => String], required = ["strategy_id"]);
+
+impl KIEarnDeallocateStatus {
+    /// Constructor returning a [KrakenInput] builder for the get deallocation status endpoint.
+    /// * `strategy_id` - ID of the earn strategy
+    pub fn build(strategy_id: String) -> Self {
+        let deallocate_status = KIEarnDeallocateStatus {
+            params: IndexMap::new(),
+        };
+        deallocate_status.with_strategy_id(strategy_id)
+    }
+
+    /// Update the earn strategy ID. Useful for templating
+    pub fn with_strategy_id(self, strategy_id: String) -> Self {
+        self.update_input("strategy_id", strategy_id)
+    }
+}
+
+impl MutateInput for KIEarnDeallocateStatus {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIEarnDeallocateStatus {}
+
+impl Input for KIEarnDeallocateStatus {
+    type Output = KOEarnStatus;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/DeallocateStatus"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/DeallocateStatus"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
@@ -0,0 +1,208 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EarnLockType, EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, Output, UpdateInput};
+
+/// Request builder for the List Earn Strategies endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIEarnStrategies {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIEarnStrategies,
+    [
+        "asset" => KAsset,
+        "lock_type[]" => [EarnLockType],
+        "ascending" => bool,
+        "cursor" => String,
+        "limit" => u32,
+    ]
+);
+
+impl KIEarnStrategies {
+    /// Constructor returning a [KrakenInput] builder for the list earn strategies endpoint.
+    pub fn build() -> Self {
+        KIEarnStrategies {
+            params: IndexMap::new(),
+        }
+    }
+
+    /// Only list strategies for the given asset
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Add a [lock type][EarnLockType] to the list of lock types to list strategies for
+    pub fn with_lock_type(self, locktype: EarnLockType) -> Self {
+        self.with_item(locktype)
+    }
+
+    /// Add several [lock types][EarnLockType] to the list of lock types to list strategies for
+    pub fn with_lock_type_list<T>(self, locktypes: T) -> Self
+    where
+        T: IntoIterator<Item = EarnLockType>,
+    {
+        self.with_item_list(locktypes)
+    }
+
+    /// Clear the list of lock types, listing strategies of every lock type
+    pub fn clear_lock_type_list(self) -> Self {
+        self.update_input("lock_type[]", String::from(""))
+    }
+
+    /// Sort the strategies in ascending order
+    pub fn ascending(self, ascending: bool) -> Self {
+        self.update_input("ascending", ascending.to_string())
+    }
+
+    /// Cursor returned by a previous request, used to fetch the next page of strategies
+    pub fn with_cursor(self, cursor: String) -> Self {
+        self.update_input("cursor", cursor)
+    }
+
+    /// Maximum number of strategies to return per page
+    pub fn with_limit(self, limit: u32) -> Self {
+        self.update_input("limit", limit.to_string())
+    }
+}
+
+impl MutateInput for KIEarnStrategies {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl IntoInputList for KIEarnStrategies {
+    fn list_name(&self) -> String {
+        String::from("lock_type[]")
+    }
+}
+
+impl InputListItem for KIEarnStrategies {
+    type ListItem = EarnLockType;
+}
+
+impl UpdateInput for KIEarnStrategies {}
+
+impl InputList for KIEarnStrategies {}
+
+impl Input for KIEarnStrategies {
+    type Output = KOEarnStrategies;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Earn/Strategies"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Earn/Strategies"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Lock up terms of an earn strategy | See [KOEarnStrategy]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnLockInfo {
+    #[serde(rename = "type")]
+    pub locktype: EarnLockType,
+    /// Seconds funds spend bonding before they start earning rewards
+    pub bonding_period: Option<i64>,
+    /// Whether the bonding period can vary
+    pub bonding_period_variable: Option<bool>,
+    /// Whether funds earn rewards while bonding
+    pub bonding_rewards: Option<bool>,
+    /// Seconds funds spend in the exit queue before unbonding
+    pub exit_queue_period: Option<i64>,
+    /// Seconds between reward payouts
+    pub payout_frequency: Option<i64>,
+    /// Seconds funds spend unbonding before they can be withdrawn
+    pub unbonding_period: Option<i64>,
+    /// Whether the unbonding period can vary
+    pub unbonding_period_variable: Option<bool>,
+    /// Whether funds earn rewards while unbonding
+    pub unbonding_rewards: Option<bool>,
+}
+
+/// Estimated annual percentage rate range | See [KOEarnStrategy]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOAprEstimate {
+    pub low: String,
+    pub high: String,
+}
+
+/// Auto compounding setting | See [KOEarnStrategy]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOAutoCompound {
+    /// One of enabled, disabled or optional
+    #[serde(rename = "type")]
+    pub compoundtype: String,
+    /// Whether auto compounding is on by default when it is optional
+    pub default: Option<bool>,
+}
+
+/// Where the strategy's yield comes from | See [KOEarnStrategy]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOYieldSource {
+    /// One of staking or off_chain
+    #[serde(rename = "type")]
+    pub sourcetype: String,
+}
+
+/// Earn strategy data | See [KOEarnStrategies]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnStrategy {
+    /// Strategy ID used when allocating or deallocating funds
+    pub id: String,
+    /// Asset earning rewards. Left as a string as Earn lists assets that KAsset doesn't know
+    pub asset: String,
+    pub lock_type: KOEarnLockInfo,
+    pub apr_estimate: Option<KOAprEstimate>,
+    /// Minimum amount that can be allocated
+    pub user_min_allocation: Option<String>,
+    /// Maximum amount that can be allocated
+    pub user_cap: Option<String>,
+    /// Fee applied when allocating
+    pub allocation_fee: String,
+    /// Fee applied when deallocating
+    pub deallocation_fee: String,
+    pub auto_compound: KOAutoCompound,
+    pub yield_source: KOYieldSource,
+    /// Whether funds can currently be allocated to this strategy
+    pub can_allocate: bool,
+    /// Whether funds can currently be deallocated from this strategy
+    pub can_deallocate: bool,
+    /// Reasons allocation is currently restricted, if any
+    pub allocation_restriction_info: Vec<String>,
+}
+
+/// Response from the List Earn Strategies endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnStrategies {
+    pub items: Vec<KOEarnStrategy>,
+    /// Cursor to pass to [with_cursor()][KIEarnStrategies::with_cursor] to fetch the next page
+    pub next_cursor: Option<String>,
+}
+
+impl Output for KOEarnStrategies {}
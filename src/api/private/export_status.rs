@@ -0,0 +1,126 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, ExportReport, KrakenInput, MethodType};
+use crate::api::timestamp::KTimestamp;
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Get Export Report Status endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIExportStatus {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(KIExportStatus, ["report" => ExportReport], required = ["report"]);
+
+impl KIExportStatus {
+    /// Constructor returning a [KrakenInput] builder for the get export report status endpoint.
+    /// * `report` - type of exports to query the status of
+    pub fn build(report: ExportReport) -> Self {
+        let export_status = KIExportStatus {
+            params: IndexMap::new(),
+        };
+        export_status.with_report(report)
+    }
+
+    /// Update the type of exports to query the status of. Useful for templating
+    pub fn with_report(self, report: ExportReport) -> Self {
+        self.update_input("report", report.to_string())
+    }
+}
+
+impl MutateInput for KIExportStatus {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIExportStatus {}
+
+impl Input for KIExportStatus {
+    type Output = KOExportStatus;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("ExportStatus"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("ExportStatus"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Processing status of an export report | See [KOExportReport]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub enum KOExportReportStatus {
+    /// Report is waiting to be processed
+    Queued,
+    /// Report is being processed
+    Processing,
+    /// Report is ready to be retrieved
+    Processed,
+}
+
+/// Export report data | See [KOExportStatus]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOExportReport {
+    /// Report ID
+    pub id: String,
+    /// Report description
+    pub descr: String,
+    /// File format of the report
+    pub format: String,
+    pub report: ExportReport,
+    pub subtype: Option<String>,
+    pub status: KOExportReportStatus,
+    pub flags: Option<String>,
+    /// Comma delimited list of fields in the report
+    pub fields: String,
+    /// Unix timestamp of when the report was requested
+    pub createdtm: KTimestamp,
+    /// Unix timestamp of when the report expires
+    pub expiretm: Option<KTimestamp>,
+    /// Unix timestamp of when the report started processing
+    pub starttm: Option<KTimestamp>,
+    /// Unix timestamp of when the report finished processing
+    pub completedtm: Option<KTimestamp>,
+    /// Unix timestamp of the start of the exported data
+    pub datastarttm: KTimestamp,
+    /// Unix timestamp of the end of the exported data
+    pub dataendtm: KTimestamp,
+    /// Asset class
+    pub aclass: Option<String>,
+    /// Comma delimited list of assets included in the report
+    pub asset: Option<String>,
+}
+
+/// Response from the Get Export Report Status endpoint
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOExportStatus {
+    /// Export reports of the requested type
+    pub reports: Vec<KOExportReport>,
+}
+
+impl Output for KOExportStatus {}
@@ -1,8 +1,10 @@
 use indexmap::map::IndexMap;
+use serde::Serialize;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KAsset, KrakenInput, LedgerType, MethodType};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, UpdateInput};
@@ -11,10 +13,23 @@ pub use super::KOLedgerInfo;
 pub use super::KOLedgers;
 
 /// Request builder for the Get Ledgers Info endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KILedgerInfo {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KILedgerInfo,
+    [
+        "asset" => [KAsset],
+        "type" => LedgerType,
+        "start" => String,
+        "end" => String,
+        "ofs" => u64,
+    ]
+);
+
 impl KILedgerInfo {
     /// Constructor returning a [KrakenInput] builder for the get ledgers info endpoint.
     pub fn build() -> Self {
@@ -48,14 +63,16 @@ impl KILedgerInfo {
         self.update_input("type", ledgertype.to_string())
     }
 
-    /// Starting Unix timestamp to filter output by. Exclusive
-    pub fn starting_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("start", timestamp.to_string())
+    /// Starting time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Exclusive
+    pub fn starting_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("start", timestamp.into().to_param())
     }
 
-    /// Ending Unix timestamp to filter output by. Inclusive
-    pub fn ending_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("end", timestamp.to_string())
+    /// Ending time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Inclusive
+    pub fn ending_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("end", timestamp.into().to_param())
     }
 
     /// Starting ledger ID to filter output by. Exclusive
@@ -72,34 +89,33 @@ impl KILedgerInfo {
     pub fn with_offset(self, offset: u64) -> Self {
         self.update_input("ofs", offset.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KILedgerInfo {
-    fn finish(self) -> KrakenInput {
+    type Output = KOLedgers;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("Ledgers"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("Ledgers"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -6,17 +6,25 @@
 //! and has been parsed into the given structure.
 //! A valid api key and api secret will have to be used when creating a
 //! [KrakenClient][super::super::client::KrakenClient] otherwise requests sent to
-//! private endpoints will panic before being sent to Kraken
+//! private endpoints will return an error before being sent to Kraken
+//!
+//! Requests to the withdrawal endpoints ([withdraw_info], [withdraw], [withdraw_status] and
+//! [withdraw_cancel]) are refused unless the client was constructed with
+//! [new_with_withdrawals()][super::super::client::KrakenClient::new_with_withdrawals]
 
+use serde::de::Deserializer;
+use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::{self, Display};
 
 // Structs/Enums
 use super::asset::{KAsset, KAssetPair};
 use super::{
-    EndpointInfo, KrakenInput, LedgerType, MethodType, OrderCloseTime, OrderFlags, OrderType,
-    TradeHistoryType, TradeType,
+    EarnLockType, EndpointInfo, ExportReport, KrakenInput, LedgerType, MethodType, OrderCloseTime,
+    OrderFlags, OrderType, TradeHistoryType, TradeType,
 };
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, Output, UpdateInput};
@@ -66,13 +74,64 @@ pub mod cancel_all_orders;
 /// Cancel all orders after ... endpoint
 pub mod cancel_on_timeout;
 
+/// Get withdrawal information endpoint
+pub mod withdraw_info;
+
+/// Withdraw funds endpoint
+pub mod withdraw;
+
+/// Get status of recent withdrawals endpoint
+pub mod withdraw_status;
+
+/// Request withdrawal cancelation endpoint
+pub mod withdraw_cancel;
+
+/// Request wallet transfer endpoint
+pub mod wallet_transfer;
+
+/// Create subaccount endpoint
+pub mod create_subaccount;
+
+/// Account transfer endpoint
+pub mod account_transfer;
+
+/// List earn strategies endpoint
+pub mod earn_strategies;
+
+/// Allocate earn funds endpoint
+pub mod earn_allocate;
+
+/// Deallocate earn funds endpoint
+pub mod earn_deallocate;
+
+/// Get allocation status endpoint
+pub mod earn_allocate_status;
+
+/// Get deallocation status endpoint
+pub mod earn_deallocate_status;
+
+/// List earn allocations endpoint
+pub mod earn_allocations;
+
+/// Request export report endpoint
+pub mod add_export;
+
+/// Get export report status endpoint
+pub mod export_status;
+
+/// Retrieve data export endpoint
+pub mod retrieve_export;
+
+/// Delete export report endpoint
+pub mod remove_export;
+
 /// Order description data | See [KOOrderInfo]
 #[derive(Deserialize, Serialize, Debug)]
 pub struct KOOrderDescription {
     pub pair: String,
     #[serde(rename = "type")]
-    pub tradetype: String,
-    pub ordertype: String,
+    pub tradetype: TradeType,
+    pub ordertype: KOOrderType,
     pub price: String,
     pub price2: String,
     pub leverage: String,
@@ -82,6 +141,160 @@ pub struct KOOrderDescription {
     pub closedesc: String,
 }
 
+// Generate an enum of the string values Kraken returns for a field, along with its conversions
+// from and into strings. Values Kraken adds in the future are kept in an `Unknown` variant so
+// they don't fail the whole response
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        ///
+        /// Values Kraken adds in the future are preserved in `Unknown`
+        #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            Unknown(String),
+        }
+
+        impl From<String> for $name {
+            fn from(val: String) -> Self {
+                match val.as_str() {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(val),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $($name::$variant => write!(f, $value),)*
+                    $name::Unknown(val) => write!(f, "{}", val),
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(val: $name) -> Self {
+                val.to_string()
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// Order type as returned from Kraken. Unlike [OrderType] the prices are not included | See
+    /// [KOOrderDescription] - [KOTradeData]
+    pub enum KOOrderType {
+        Market => "market",
+        Limit => "limit",
+        StopLoss => "stop-loss",
+        TakeProfit => "take-profit",
+        StopLossLimit => "stop-loss-limit",
+        TakeProfitLimit => "take-profit-limit",
+        SettlePosition => "settle-position",
+    }
+}
+
+string_enum! {
+    /// Position status of a trade | See [KOTradeData]
+    pub enum KOPositionStatus {
+        Open => "open",
+        Closed => "closed",
+    }
+}
+
+string_enum! {
+    /// Miscellaneous info of an order or trade | See [KOOrderInfo] - [KOTradeData]
+    pub enum KOMiscInfo {
+        /// Triggered by stop price
+        Stopped => "stopped",
+        /// Triggered by touch price
+        Touched => "touched",
+        /// Liquidation
+        Liquidated => "liquidated",
+        /// Partial fill
+        Partial => "partial",
+    }
+}
+
+string_enum! {
+    /// Order flag as returned from Kraken | See [KOOrderInfo]
+    pub enum KOOrderFlag {
+        /// Volume in quote currency
+        VolumeInQuote => "viqc",
+        /// Prefer fee in base currency
+        BaseCurrency => "fcib",
+        /// Prefer fee in quote currency
+        QuoteCurrency => "fciq",
+        /// No market price protection
+        NoMarketPriceProtection => "nompp",
+        /// Post only order
+        PostOnly => "post",
+    }
+}
+
+/// Comma delimited list returned from Kraken, parsed into its items | See [KOMiscInfo] -
+/// [KOOrderFlag]
+///
+/// Serializes back into the same comma delimited string
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KOFlagList<T>(pub Vec<T>);
+
+impl<T: PartialEq> KOFlagList<T> {
+    /// Returns true if the list contains `flag`
+    pub fn contains(&self, flag: &T) -> bool {
+        self.0.contains(flag)
+    }
+
+    /// Returns true if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Display> Display for KOFlagList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((first, flags)) = self.0.split_first() {
+            write!(f, "{}", first)?;
+            for flag in flags {
+                write!(f, ",{}", flag)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display> Serialize for KOFlagList<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, T: From<String>> Deserialize<'de> for KOFlagList<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let buf = String::deserialize(deserializer)?;
+
+        Ok(KOFlagList(
+            buf.split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(|flag| T::from(flag.to_string()))
+                .collect(),
+        ))
+    }
+}
+
 /// Order status data | See [KOOrderInfo]
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "lowercase")]
@@ -110,11 +323,11 @@ pub struct KOOrderInfo {
     /// status of order:
     pub status: KOOrderStatus,
     /// unix timestamp of when order was placed
-    pub opentm: f64,
+    pub opentm: KTimestamp,
     /// unix timestamp of order start time (or 0 if not set)
-    pub starttm: f64,
+    pub starttm: KTimestamp,
     /// unix timestamp of order end time (or 0 if not set)
-    pub expiretm: f64,
+    pub expiretm: KTimestamp,
     /// order description info
     pub descr: KOOrderDescription,
     /// volume of order (base currency unless viqc set in oflags)
@@ -131,23 +344,16 @@ pub struct KOOrderInfo {
     pub stopprice: Option<String>,
     /// triggered limit price (quote currency, when limit based order type triggered)
     pub limitprice: Option<String>,
-    /// comma delimited list of miscellaneous info:
-    /// + stopped = triggered by stop price
-    /// + touched = triggered by touch price
-    /// + liquidated = liquidation
-    /// + partial = partial fill
-    pub misc: String,
-    /// comma delimited list of order flags:
-    /// + viqc = volume in quote currency
-    /// + fcib = prefer fee in base currency (default if selling)
-    /// + fciq = prefer fee in quote currency (default if buying)
-    /// + nompp = no market price protection
-    pub oflags: String,
+    /// miscellaneous info
+    pub misc: KOFlagList<KOMiscInfo>,
+    /// order flags. Kraken defaults to preferring the fee in the base currency when selling and
+    /// in the quote currency when buying
+    pub oflags: KOFlagList<KOOrderFlag>,
     /// array of trade ids related to order (if trades info requested and data available)
     pub trades: Option<Vec<String>>,
     /// unix timestamp of when order was closed. Field only present when calling ClosedOrders
     /// endpoint
-    pub closetm: Option<f64>,
+    pub closetm: Option<KTimestamp>,
     /// additional info on status (if any). Field only present when calling ClosedOrders
     pub reason: Option<String>,
 }
@@ -159,23 +365,31 @@ pub struct KOTradeData {
     /// Order responsible for execution of trade
     pub ordertxid: String,
     pub pair: String,
-    pub time: f64,
+    pub time: KTimestamp,
     #[serde(rename = "type")]
-    pub tradetype: String,
-    pub ordertype: String,
+    pub tradetype: TradeType,
+    pub ordertype: KOOrderType,
     pub price: String,
     pub cost: String,
     pub fee: String,
     pub vol: String,
     pub margin: Option<String>,
-    pub misc: String,
-    pub posstatus: Option<String>,
+    /// miscellaneous info
+    pub misc: KOFlagList<KOMiscInfo>,
+    /// position status (only present if the trade opened a position)
+    pub posstatus: Option<KOPositionStatus>,
+    /// average price of the closed portion of the position (quote currency)
     pub cprice: Option<String>,
+    /// total fee of the closed portion of the position (quote currency)
     pub cfee: Option<String>,
+    /// total volume of the closed portion of the position
     pub cvol: Option<String>,
+    /// total margin freed in the closed portion of the position (quote currency)
     pub cmargin: Option<String>,
+    /// net profit/loss of the closed portion of the position (quote currency)
     pub net: Option<String>,
-    pub trades: Option<String>,
+    /// trade ids of the trades closing the position
+    pub trades: Option<Vec<String>>,
 }
 
 /// Ledger info data | See [KOLedgers]
@@ -183,7 +397,7 @@ pub struct KOTradeData {
 pub struct KOLedgerInfo {
     /// Order responsible for execution of trade
     pub refid: String,
-    pub time: f64,
+    pub time: KTimestamp,
     #[serde(rename = "type")]
     pub ledgertype: String,
     pub aclass: String,
@@ -204,3 +418,93 @@ pub struct KOLedgers {
 }
 
 impl Output for KOLedgers {}
+
+/// Status of a pending earn allocation or deallocation | See
+/// [KIEarnAllocateStatus][earn_allocate_status::KIEarnAllocateStatus] -
+/// [KIEarnDeallocateStatus][earn_deallocate_status::KIEarnDeallocateStatus]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOEarnStatus {
+    /// Whether the request is still being processed
+    pub pending: bool,
+}
+
+impl Output for KOEarnStatus {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_trade_data() {
+        let json = r#"{"ordertxid":"OQCLML-BW3P3-BUCMWZ","pair":"XXBTZUSD","time":1616669193.9817,
+            "type":"sell","ordertype":"iceberg","price":"52000.0","cost":"520.0","fee":"0.832",
+            "vol":"0.01","margin":"104.0","misc":"closing,partial","posstatus":"closed",
+            "cprice":"51000.0","cfee":"0.5","cvol":"0.01","cmargin":"104.0","net":"10.0",
+            "trades":["TCWJEG-FL4SZ-3FKGH6"]}"#;
+
+        let trade: KOTradeData = serde_json::from_str(json).unwrap();
+        assert_eq!(trade.tradetype, TradeType::Sell);
+        assert_eq!(trade.ordertype, KOOrderType::Unknown(String::from("iceberg")));
+        assert_eq!(trade.posstatus, Some(KOPositionStatus::Closed));
+        assert!(trade.misc.contains(&KOMiscInfo::Partial));
+        assert!(trade.misc.contains(&KOMiscInfo::Unknown(String::from("closing"))));
+
+        let value = serde_json::to_value(&trade).unwrap();
+        assert_eq!(value["ordertype"], "iceberg");
+        assert_eq!(value["misc"], "closing,partial");
+
+        let flags: KOFlagList<KOOrderFlag> = serde_json::from_str(r#""viqc,post""#).unwrap();
+        assert_eq!(flags, KOFlagList(vec![KOOrderFlag::VolumeInQuote, KOOrderFlag::PostOnly]));
+        let empty: KOFlagList<KOOrderFlag> = serde_json::from_value(serde_json::json!("")).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn earn_outputs() {
+        use super::earn_allocate::KOEarnAllocate;
+        use super::earn_allocations::KOEarnAllocations;
+        use super::earn_strategies::KOEarnStrategies;
+
+        let json = r#"{"items":[{"id":"ESRFUO3-Q62XD-WIOIL7","asset":"DOT",
+            "lock_type":{"type":"bonded","bonding_period":604800,"unbonding_period":2419200,
+            "payout_frequency":604800},"apr_estimate":{"low":"8.0000","high":"12.0000"},
+            "user_min_allocation":"0.01","allocation_fee":"0.0000","deallocation_fee":"0.0000",
+            "auto_compound":{"type":"enabled"},"yield_source":{"type":"staking"},
+            "can_allocate":true,"can_deallocate":false,
+            "allocation_restriction_info":["tier"]}],"next_cursor":"2"}"#;
+        let strategies: KOEarnStrategies = serde_json::from_str(json).unwrap();
+        let strategy = &strategies.items[0];
+        assert_eq!(strategy.id, "ESRFUO3-Q62XD-WIOIL7");
+        assert!(matches!(strategy.lock_type.locktype, EarnLockType::Bonded));
+        assert_eq!(strategy.lock_type.unbonding_period, Some(2_419_200));
+        assert_eq!(strategy.lock_type.bonding_rewards, None);
+        assert_eq!(strategy.apr_estimate.as_ref().unwrap().high, "12.0000");
+        assert_eq!(strategy.user_cap, None);
+        assert!(!strategy.can_deallocate);
+        assert_eq!(strategies.next_cursor.as_deref(), Some("2"));
+
+        let json = r#"{"converted_asset":"USD","total_allocated":"49.2398",
+            "total_rewarded":"0.0675","next_cursor":null,"items":[{
+            "strategy_id":"ESDQCOL-WTZEU-NU55QF","native_asset":"ETH","amount_allocated":{
+            "bonding":{"native":"0.0210000000","converted":"39.0645","allocation_count":1,
+            "allocations":[{"created_at":"2023-07-06T10:52:05Z",
+            "expires":"2023-08-19T02:34:05.807Z","native":"0.0210000000","converted":"39.0645"}]},
+            "total":{"native":"0.0210000000","converted":"39.0645"}},
+            "total_rewarded":{"native":"0","converted":"0.0000"}}]}"#;
+        let allocations: KOEarnAllocations = serde_json::from_str(json).unwrap();
+        assert_eq!(allocations.converted_asset, "USD");
+        let allocation = &allocations.items[0];
+        assert_eq!(allocation.native_asset, "ETH");
+        assert!(allocation.payout.is_none());
+        let bonding = allocation.amount_allocated.bonding.as_ref().unwrap();
+        let entry = &bonding.allocations.as_ref().unwrap()[0];
+        assert_eq!(entry.created_at.unix_seconds(), 1_688_640_725);
+        assert_eq!(entry.expires.subsec_nanos(), 807_000_000);
+        assert_eq!(allocation.amount_allocated.total.converted, "39.0645");
+
+        let allocate: KOEarnAllocate = serde_json::from_str("true").unwrap();
+        assert!(allocate.accepted);
+        let status: KOEarnStatus = serde_json::from_str(r#"{"pending":true}"#).unwrap();
+        assert!(status.pending);
+    }
+}
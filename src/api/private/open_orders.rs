@@ -1,8 +1,8 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -14,10 +14,14 @@ pub use super::KOOrderInfo;
 pub use super::KOOrderStatus;
 
 /// Request builder for the Get Open Orders endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIOpenOrders {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIOpenOrders, ["trades" => bool, "userref" => u32]);
+
 impl KIOpenOrders {
     /// Constructor returning a [KrakenInput] builder for the get open orders endpoint.
     pub fn build() -> Self {
@@ -42,34 +46,33 @@ impl KIOpenOrders {
     pub fn with_userref(self, userref: u32) -> Self {
         self.update_input("userref", userref.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIOpenOrders {
-    fn finish(self) -> KrakenInput {
+    type Output = KOOpenOrders;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("OpenOrders"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("OpenOrders"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -1,19 +1,35 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
-use super::{EndpointInfo, KrakenInput, MethodType};
+use super::{
+    EndpointInfo, KOFlagList, KOMiscInfo, KOOrderFlag, KOOrderType, KrakenInput, MethodType,
+    TradeType,
+};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Get Open Positions endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIOpenPositions {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KIOpenPositions,
+    [
+        "txid" => String,
+        "docalcs" => bool,
+        "consolidation" => String,
+    ],
+    required = ["txid"]
+);
+
 impl KIOpenPositions {
     /// Constructor returning a [KrakenInput] builder for the get open positions endpoint.
     /// * `txid` is the transaction ID to query order info for
@@ -61,34 +77,33 @@ impl KIOpenPositions {
     pub fn consolidate(self) -> Self {
         self.update_input("consolidation", String::from("market"))
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIOpenPositions {
-    fn finish(self) -> KrakenInput {
+    type Output = KOOpenPositions;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("OpenPositions"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("OpenPositions"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -119,10 +134,10 @@ pub struct KOPositionInfo {
     /// Order responsible for execution of trade
     pub ordertxid: String,
     pub pair: String,
-    pub time: f64,
+    pub time: KTimestamp,
     #[serde(rename = "type")]
-    pub tradetype: String,
-    pub ordertype: String,
+    pub tradetype: TradeType,
+    pub ordertype: KOOrderType,
     pub cost: String,
     pub fee: String,
     pub vol: String,
@@ -130,8 +145,8 @@ pub struct KOPositionInfo {
     pub margin: Option<String>,
     pub value: Option<String>,
     pub net: Option<String>,
-    pub misc: String,
-    pub oflags: Option<String>,
+    pub misc: KOFlagList<KOMiscInfo>,
+    pub oflags: Option<KOFlagList<KOOrderFlag>>,
 }
 
 /// Response from the Get Open Positions endpoint
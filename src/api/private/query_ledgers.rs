@@ -1,6 +1,7 @@
 use indexmap::map::IndexMap;
+use serde::Serialize;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -11,10 +12,14 @@ pub use super::KOLedgerInfo;
 pub use super::KOLedgers;
 
 /// Request builder for the Query Ledgers endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIQueryLedgers {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIQueryLedgers, ["id" => String], required = ["id"]);
+
 impl KIQueryLedgers {
     /// Constructor returning a [KrakenInput] builder for the query ledgers endpoint.
     /// * `ledgerid` is the ledger ID to query info for
@@ -46,34 +51,33 @@ impl KIQueryLedgers {
         self.update_input("id", String::from(""))
             .with_item_list(ledgerids)
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIQueryLedgers {
-    fn finish(self) -> KrakenInput {
+    type Output = KOLedgers;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("QueryLedgers"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("QueryLedgers"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -1,8 +1,8 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -14,10 +14,22 @@ pub use super::KOOrderInfo;
 pub use super::KOOrderStatus;
 
 /// Request builder for the Query Orders Info endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIQueryOrders {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KIQueryOrders,
+    [
+        "txid" => String,
+        "trades" => bool,
+        "userref" => u32,
+    ],
+    required = ["txid"]
+);
+
 impl KIQueryOrders {
     /// Constructor returning a [KrakenInput] builder for the query orders info endpoint.
     /// * `txid` is the transaction ID to query order info for
@@ -64,34 +76,33 @@ impl KIQueryOrders {
     pub fn with_userref(self, userref: u32) -> Self {
         self.update_input("userref", userref.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KIQueryOrders {
-    fn finish(self) -> KrakenInput {
+    type Output = KOQueryOrders;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("QueryOrders"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("QueryOrders"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
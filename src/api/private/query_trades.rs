@@ -1,8 +1,8 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType};
 
@@ -12,10 +12,14 @@ use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, Output,
 pub use super::KOTradeData;
 
 /// Request builder for the Query Trades Info endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KITradesInfo {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KITradesInfo, ["txid" => String, "trades" => bool], required = ["txid"]);
+
 impl KITradesInfo {
     /// Constructor returning a [KrakenInput] builder for the query trades info endpoint.
     /// * `txid` is the transaction ID to query order info for
@@ -56,34 +60,33 @@ impl KITradesInfo {
             self.update_input("trades", String::from(""))
         }
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KITradesInfo {
-    fn finish(self) -> KrakenInput {
+    type Output = KOTradesInfo;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("QueryTrades"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("QueryTrades"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -0,0 +1,115 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// How to remove an export report
+pub enum RemoveExportType {
+    /// Cancel a report that is queued or being processed
+    Cancel,
+    /// Delete a report that has been processed
+    Delete,
+}
+
+impl Display for RemoveExportType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoveExportType::Cancel => write!(f, "cancel"),
+            RemoveExportType::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+variant_params!(RemoveExportType, [RemoveExportType::Cancel, RemoveExportType::Delete]);
+
+/// Request builder for the Delete Export Report endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIRemoveExport {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIRemoveExport,
+    [
+        "id" => String,
+        "type" => RemoveExportType,
+    ],
+    required = ["id", "type"]
+);
+
+impl KIRemoveExport {
+    /// Constructor returning a [KrakenInput] builder for the delete export report endpoint.
+    /// * `id` - report ID to remove
+    /// * `removetype` - [RemoveExportType]
+    pub fn build(id: String, removetype: RemoveExportType) -> Self {
+        let remove_export = KIRemoveExport {
+            params: IndexMap::new(),
+        };
+        remove_export.with_id(id).with_remove_type(removetype)
+    }
+
+    /// Update the report ID to remove. Useful for templating
+    pub fn with_id(self, id: String) -> Self {
+        self.update_input("id", id)
+    }
+
+    /// Update how the report should be removed. Useful for templating
+    pub fn with_remove_type(self, removetype: RemoveExportType) -> Self {
+        self.update_input("type", removetype.to_string())
+    }
+}
+
+impl MutateInput for KIRemoveExport {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIRemoveExport {}
+
+impl Input for KIRemoveExport {
+    type Output = KORemoveExport;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("RemoveExport"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("RemoveExport"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Delete Export Report endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KORemoveExport {
+    /// Whether the deletion was successful. Only present when deleting
+    pub delete: Option<bool>,
+    /// Whether the cancelation was successful. Only present when canceling
+    pub cancel: Option<bool>,
+}
+
+impl Output for KORemoveExport {}
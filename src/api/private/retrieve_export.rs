@@ -0,0 +1,206 @@
+use csv::ReaderBuilder;
+use indexmap::map::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::marker::PhantomData;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+// Structs/Enums
+use super::{EndpointInfo, KrakenInput, MethodType};
+use crate::api::KrakenResult;
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+pub use super::KOLedgerInfo;
+pub use super::KOTradeData;
+
+/// Request builder for the Retrieve Data Export endpoint
+///
+/// # Note
+/// Kraken responds to this endpoint with a zip archive rather than json. Send it with
+/// [request_export()][crate::client::KrakenClient::request_export] instead of
+/// [request()][crate::client::KrakenClient::request]
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIRetrieveExport {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(KIRetrieveExport, ["id" => String], required = ["id"]);
+
+impl KIRetrieveExport {
+    /// Constructor returning a [KrakenInput] builder for the retrieve data export endpoint.
+    /// * `id` - report ID to retrieve
+    pub fn build(id: String) -> Self {
+        let retrieve_export = KIRetrieveExport {
+            params: IndexMap::new(),
+        };
+        retrieve_export.with_id(id)
+    }
+
+    /// Update the report ID to retrieve. Useful for templating
+    pub fn with_id(self, id: String) -> Self {
+        self.update_input("id", id)
+    }
+}
+
+impl MutateInput for KIRetrieveExport {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIRetrieveExport {}
+
+impl Input for KIRetrieveExport {
+    type Output = KORetrieveExport;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("RetrieveExport"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("RetrieveExport"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Retrieve Data Export endpoint
+#[derive(Debug)]
+pub struct KORetrieveExport {
+    /// Zip archive containing the report
+    pub data: Vec<u8>,
+}
+
+impl Output for KORetrieveExport {}
+
+impl KORetrieveExport {
+    // Largest uncompressed report read, in bytes. The size the archive records for the report
+    // can't be trusted, so it isn't used to allocate and the read itself is bounded
+    const MAX_REPORT_SIZE: u64 = 1 << 30;
+
+    /// Unzip a trades report and parse it into a map with the trade's transaction ID as the key
+    /// and the trade info as the value
+    pub fn parse_trades(&self) -> KrakenResult<HashMap<String, KOTradeData>> {
+        self.parse_report()
+    }
+
+    /// Unzip a ledgers report and parse it into a map with the ledger ID as the key and the
+    /// ledger info as the value
+    pub fn parse_ledgers(&self) -> KrakenResult<HashMap<String, KOLedgerInfo>> {
+        self.parse_report()
+    }
+
+    fn parse_report<T>(&self) -> KrakenResult<HashMap<String, T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut archive = ZipArchive::new(Cursor::new(&self.data))?;
+        // Kraken packs a single report file into each archive
+        let mut file = archive.by_index(0)?;
+        let delimiter = if file.name().ends_with(".tsv") { b'\t' } else { b',' };
+        let contents = read_report(&mut file, KORetrieveExport::MAX_REPORT_SIZE)?;
+
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(contents.as_slice());
+        let headers = reader.headers()?.clone();
+
+        let mut records = HashMap::new();
+        for record in reader.records() {
+            let record = record?;
+            let id: ExportId = record.deserialize(Some(&headers))?;
+            records.insert(id.txid, record.deserialize(Some(&headers))?);
+        }
+
+        Ok(records)
+    }
+}
+
+// Read at most `limit` bytes of a report, failing if it is longer
+fn read_report<R: Read>(report: R, limit: u64) -> Result<Vec<u8>, ZipError> {
+    let mut contents = Vec::new();
+    report
+        .take(limit + 1)
+        .read_to_end(&mut contents)
+        .map_err(ZipError::Io)?;
+    match contents.len() as u64 > limit {
+        true => Err(ZipError::InvalidArchive("Export report is too large")),
+        false => Ok(contents),
+    }
+}
+
+#[derive(Deserialize)]
+struct ExportId {
+    txid: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::timestamp::KTimestamp;
+    use std::io::Write;
+    use zip::write::{FileOptions, ZipWriter};
+
+    fn archive(name: &str, report: &str) -> KORetrieveExport {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file(name, FileOptions::default()).unwrap();
+        writer.write_all(report.as_bytes()).unwrap();
+        KORetrieveExport {
+            data: writer.finish().unwrap().into_inner(),
+        }
+    }
+
+    #[test]
+    fn parse_exports() {
+        let trades = archive(
+            "trades.csv",
+            "\"txid\",\"ordertxid\",\"pair\",\"time\",\"type\",\"ordertype\",\"price\",\"cost\",\"fee\",\"vol\",\"margin\",\"misc\",\"ledgers\"\n\
+             \"TCWJEG-FL4SZ-3FKGH6\",\"OQCLML-BW3P3-BUCMWZ\",\"XXBTZUSD\",\"2021-03-25 10:46:33.9817\",\"buy\",\"limit\",\"52000.0\",\"520.0\",\"0.832\",\"0.01\",\"0.0\",\"\",\"L4UESK-KG3EQ-UFO4T5\"\n",
+        )
+        .parse_trades()
+        .unwrap();
+        let trade = &trades["TCWJEG-FL4SZ-3FKGH6"];
+        assert_eq!(trade.ordertxid, "OQCLML-BW3P3-BUCMWZ");
+        assert_eq!(trade.time, KTimestamp::from_unix(1616669193, 981_700_000));
+        assert_eq!(trade.posstatus, None);
+
+        let ledgers = archive(
+            "ledgers.tsv",
+            "txid\trefid\ttime\ttype\tsubtype\taclass\tasset\tamount\tfee\tbalance\n\
+             L4UESK-KG3EQ-UFO4T5\tTCWJEG-FL4SZ-3FKGH6\t2021-03-25 10:46:33\ttrade\t\tcurrency\tXXBT\t0.01\t0\t0.01\n",
+        )
+        .parse_ledgers()
+        .unwrap();
+        let ledger = &ledgers["L4UESK-KG3EQ-UFO4T5"];
+        assert_eq!(ledger.refid, "TCWJEG-FL4SZ-3FKGH6");
+        assert_eq!(ledger.time, KTimestamp::from_unix(1616669193, 0));
+        assert_eq!(ledger.balance, Some(String::from("0.01")));
+
+        assert_eq!(read_report(&b"txid"[..], 4).unwrap(), b"txid");
+        assert!(matches!(
+            read_report(&b"txid\n"[..], 4),
+            Err(ZipError::InvalidArchive(_))
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
 
@@ -9,10 +9,14 @@ use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
 use super::{Input, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Get Trade Balance endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KITradeBalance {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KITradeBalance, ["asset" => KAsset]);
+
 impl KITradeBalance {
     /// Constructor returning a [KrakenInput] builder for the get trade balance endpoint.
     pub fn build() -> KITradeBalance {
@@ -25,10 +29,6 @@ impl KITradeBalance {
     pub fn with_asset(self, asset: KAsset) -> Self {
         self.update_input("asset", asset.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl MutateInput for KITradeBalance {
@@ -40,27 +40,30 @@ impl MutateInput for KITradeBalance {
 impl UpdateInput for KITradeBalance {}
 
 impl Input for KITradeBalance {
-    fn finish(self) -> KrakenInput {
+    type Output = KOTradeBalance;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("TradeBalance"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("TradeBalance"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
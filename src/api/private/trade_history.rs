@@ -1,10 +1,11 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KrakenInput, MethodType, TradeHistoryType};
+use crate::api::timestamp::KTimestamp;
 
 // Traits
 use super::{Input, MutateInput, Output, UpdateInput};
@@ -12,10 +13,23 @@ use super::{Input, MutateInput, Output, UpdateInput};
 pub use super::KOTradeData;
 
 /// Request builder for the Get Trades History endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KITradeHistory {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KITradeHistory,
+    [
+        "type" => TradeHistoryType,
+        "trades" => bool,
+        "start" => String,
+        "end" => String,
+        "ofs" => u64,
+    ]
+);
+
 impl KITradeHistory {
     /// Constructor returning a [KrakenInput] builder for the get trades history endpoint.
     pub fn build() -> Self {
@@ -38,14 +52,16 @@ impl KITradeHistory {
         }
     }
 
-    /// Starting Unix timestamp to filter output by. Exclusive
-    pub fn starting_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("start", timestamp.to_string())
+    /// Starting time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Exclusive
+    pub fn starting_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("start", timestamp.into().to_param())
     }
 
-    /// Ending Unix timestamp to filter output by. Inclusive
-    pub fn ending_timestamp(self, timestamp: u64) -> Self {
-        self.update_input("end", timestamp.to_string())
+    /// Ending time to filter output by, such as a Unix timestamp in seconds or a
+    /// [SystemTime][std::time::SystemTime]. Inclusive
+    pub fn ending_timestamp<T: Into<KTimestamp>>(self, timestamp: T) -> Self {
+        self.update_input("end", timestamp.into().to_param())
     }
 
     /// Starting transaction ID to filter output by. Exclusive
@@ -62,34 +78,33 @@ impl KITradeHistory {
     pub fn with_offset(self, offset: u64) -> Self {
         self.update_input("ofs", offset.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl Input for KITradeHistory {
-    fn finish(self) -> KrakenInput {
+    type Output = KOTradeHistory;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("TradesHistory"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("TradesHistory"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
@@ -109,4 +124,15 @@ pub struct KOTradeHistory {
     pub count: u32,
 }
 
+impl KOTradeHistory {
+    /// Trades executed by the order with transaction ID `ordertxid`, as a map with the trade's
+    /// transaction ID as the key and the trade info as the value
+    pub fn trades_for_order(&self, ordertxid: &str) -> HashMap<&String, &KOTradeData> {
+        self.closed
+            .iter()
+            .filter(|(_, trade)| trade.ordertxid == ordertxid)
+            .collect()
+    }
+}
+
 impl Output for KOTradeHistory {}
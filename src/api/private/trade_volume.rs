@@ -1,8 +1,8 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use crate::auth::KrakenAuth;
 // Structs/Enums
 use super::{EndpointInfo, KAssetPair, KrakenInput, MethodType};
 
@@ -10,10 +10,14 @@ use super::{EndpointInfo, KAssetPair, KrakenInput, MethodType};
 use super::{Input, InputList, InputListItem, IntoInputList, MutateInput, Output, UpdateInput};
 
 /// Request builder for the Get Trade Volume endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KITradeVolume {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KITradeVolume, ["pair" => [KAssetPair], "fee-info" => bool]);
+
 impl KITradeVolume {
     /// Constructor returning a [KrakenInput] builder for the get trade volume endpoint.
     pub fn build() -> Self {
@@ -46,10 +50,6 @@ impl KITradeVolume {
     pub fn with_fee_info(self, feeinfo: bool) -> Self {
         self.update_input("fee-info", feeinfo.to_string())
     }
-
-    fn with_nonce(self) -> Self {
-        self.update_input("nonce", KrakenAuth::nonce())
-    }
 }
 
 impl MutateInput for KITradeVolume {
@@ -73,27 +73,30 @@ impl InputListItem for KITradeVolume {
 impl InputList for KITradeVolume {}
 
 impl Input for KITradeVolume {
-    fn finish(self) -> KrakenInput {
+    type Output = KOTradeVolume;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Private,
                 endpoint: String::from("TradeVolume"),
             },
-            params: Some(self.with_nonce().params),
+            params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
-        let newself = self.with_nonce();
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
                     methodtype: MethodType::Private,
                     endpoint: String::from("TradeVolume"),
                 },
-                params: Some(newself.params.clone()),
+                params: Some(self.params.clone()),
+                output: PhantomData,
             },
-            newself,
+            self,
         )
     }
 }
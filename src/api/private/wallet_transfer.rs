@@ -0,0 +1,122 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+use crate::api::ParamValue;
+
+// The only wallets Kraken transfers between, and so the only values of `from` and `to`
+const SPOT_WALLET: &str = "Spot Wallet";
+const FUTURES_WALLET: &str = "Futures Wallet";
+
+struct SpotWallet;
+
+impl ParamValue for SpotWallet {
+    fn is_valid(value: &str) -> bool {
+        value == SPOT_WALLET
+    }
+}
+
+struct FuturesWallet;
+
+impl ParamValue for FuturesWallet {
+    fn is_valid(value: &str) -> bool {
+        value == FUTURES_WALLET
+    }
+}
+
+/// Request builder for the Request Wallet Transfer endpoint
+///
+/// Kraken only supports transfers from the spot wallet to the futures wallet
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIWalletTransfer {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIWalletTransfer,
+    [
+        "from" => SpotWallet,
+        "to" => FuturesWallet,
+        "asset" => KAsset,
+        "amount" => f64,
+    ],
+    required = ["from", "to", "asset", "amount"]
+);
+
+impl KIWalletTransfer {
+    /// Constructor returning a [KrakenInput] builder for the request wallet transfer endpoint.
+    /// * `asset` - asset to transfer
+    /// * `amount` - amount to transfer from the spot wallet to the futures wallet
+    pub fn build(asset: KAsset, amount: f64) -> Self {
+        let wallet_transfer = KIWalletTransfer {
+            params: IndexMap::new(),
+        };
+        wallet_transfer
+            .with_asset(asset)
+            .update_input("from", String::from(SPOT_WALLET))
+            .update_input("to", String::from(FUTURES_WALLET))
+            .with_amount(amount)
+    }
+
+    /// Update the asset to transfer. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Update the amount to transfer. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+}
+
+impl MutateInput for KIWalletTransfer {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIWalletTransfer {}
+
+impl Input for KIWalletTransfer {
+    type Output = KOWalletTransfer;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("WalletTransfer"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("WalletTransfer"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Request Wallet Transfer endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOWalletTransfer {
+    /// Reference ID of the transfer
+    pub refid: String,
+}
+
+impl Output for KOWalletTransfer {}
@@ -0,0 +1,104 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Withdraw Funds endpoint
+///
+/// # Note
+/// Withdrawal requests are refused by a [KrakenClient][crate::client::KrakenClient] unless it was
+/// constructed with [new_with_withdrawals()][crate::client::KrakenClient::new_with_withdrawals]
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIWithdraw {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIWithdraw,
+    [
+        "asset" => KAsset,
+        "key" => String,
+        "amount" => f64,
+    ],
+    required = ["asset", "key", "amount"]
+);
+
+impl KIWithdraw {
+    /// Constructor returning a [KrakenInput] builder for the withdraw funds endpoint.
+    /// * `asset` - asset being withdrawn
+    /// * `key` - withdrawal key name, as set up on your account
+    /// * `amount` - amount to be withdrawn
+    pub fn build(asset: KAsset, key: String, amount: f64) -> Self {
+        let withdraw = KIWithdraw {
+            params: IndexMap::new(),
+        };
+        withdraw.with_asset(asset).with_key(key).with_amount(amount)
+    }
+
+    /// Update the asset being withdrawn. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Update the withdrawal key name. Useful for templating
+    pub fn with_key(self, key: String) -> Self {
+        self.update_input("key", key)
+    }
+
+    /// Update the amount to be withdrawn. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+}
+
+impl MutateInput for KIWithdraw {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIWithdraw {}
+
+impl Input for KIWithdraw {
+    type Output = KOWithdraw;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("Withdraw"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("Withdraw"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Withdraw Funds endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOWithdraw {
+    /// Reference ID of the withdrawal
+    pub refid: String,
+}
+
+impl Output for KOWithdraw {}
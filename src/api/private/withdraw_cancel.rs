@@ -0,0 +1,95 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Request Withdrawal Cancelation endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIWithdrawCancel {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIWithdrawCancel,
+    [
+        "asset" => KAsset,
+        "refid" => String,
+    ],
+    required = ["asset", "refid"]
+);
+
+impl KIWithdrawCancel {
+    /// Constructor returning a [KrakenInput] builder for the request withdrawal cancelation
+    /// endpoint.
+    /// * `asset` - asset that was withdrawn
+    /// * `refid` - reference ID of the withdrawal to cancel
+    pub fn build(asset: KAsset, refid: String) -> Self {
+        let withdraw_cancel = KIWithdrawCancel {
+            params: IndexMap::new(),
+        };
+        withdraw_cancel.with_asset(asset).with_refid(refid)
+    }
+
+    /// Update the asset that was withdrawn. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Update the reference ID of the withdrawal to cancel. Useful for templating
+    pub fn with_refid(self, refid: String) -> Self {
+        self.update_input("refid", refid)
+    }
+}
+
+impl MutateInput for KIWithdrawCancel {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIWithdrawCancel {}
+
+impl Input for KIWithdrawCancel {
+    type Output = KOWithdrawCancel;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("WithdrawCancel"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("WithdrawCancel"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Request Withdrawal Cancelation endpoint
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOWithdrawCancel {
+    /// Whether the cancelation was successfully submitted
+    pub canceled: bool,
+}
+
+impl Output for KOWithdrawCancel {}
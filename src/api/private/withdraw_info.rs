@@ -0,0 +1,109 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Get Withdrawal Information endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIWithdrawInfo {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIWithdrawInfo,
+    [
+        "asset" => KAsset,
+        "key" => String,
+        "amount" => f64,
+    ],
+    required = ["asset", "key", "amount"]
+);
+
+impl KIWithdrawInfo {
+    /// Constructor returning a [KrakenInput] builder for the get withdrawal information endpoint.
+    /// * `asset` - asset being withdrawn
+    /// * `key` - withdrawal key name, as set up on your account
+    /// * `amount` - amount to be withdrawn
+    pub fn build(asset: KAsset, key: String, amount: f64) -> Self {
+        let withdraw_info = KIWithdrawInfo {
+            params: IndexMap::new(),
+        };
+        withdraw_info
+            .with_asset(asset)
+            .with_key(key)
+            .with_amount(amount)
+    }
+
+    /// Update the asset being withdrawn. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Update the withdrawal key name. Useful for templating
+    pub fn with_key(self, key: String) -> Self {
+        self.update_input("key", key)
+    }
+
+    /// Update the amount to be withdrawn. Useful for templating
+    pub fn with_amount(self, amount: f64) -> Self {
+        self.update_input("amount", amount.to_string())
+    }
+}
+
+impl MutateInput for KIWithdrawInfo {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIWithdrawInfo {}
+
+impl Input for KIWithdrawInfo {
+    type Output = KOWithdrawInfo;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("WithdrawInfo"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("WithdrawInfo"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Response from the Get Withdrawal Information endpoint
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOWithdrawInfo {
+    /// Name of the withdrawal method that will be used
+    pub method: String,
+    /// Maximum net amount that can be withdrawn right now
+    pub limit: String,
+    /// Net amount that will be sent, after fees
+    pub amount: String,
+    /// Amount of fees that will be paid
+    pub fee: String,
+}
+
+impl Output for KOWithdrawInfo {}
@@ -0,0 +1,127 @@
+use indexmap::map::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+// Structs/Enums
+use super::{EndpointInfo, KAsset, KrakenInput, MethodType};
+use crate::api::timestamp::KTimestamp;
+
+// Traits
+use super::{Input, MutateInput, Output, UpdateInput};
+
+/// Request builder for the Get Status of Recent Withdrawals endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct KIWithdrawStatus {
+    params: IndexMap<String, String>,
+}
+
+deserialize_params!(
+    KIWithdrawStatus,
+    [
+        "asset" => KAsset,
+        "method" => String,
+    ],
+    required = ["asset"]
+);
+
+impl KIWithdrawStatus {
+    /// Constructor returning a [KrakenInput] builder for the get status of recent withdrawals
+    /// endpoint.
+    /// * `asset` - asset to query withdrawals for
+    pub fn build(asset: KAsset) -> Self {
+        let withdraw_status = KIWithdrawStatus {
+            params: IndexMap::new(),
+        };
+        withdraw_status.with_asset(asset)
+    }
+
+    /// Update the asset to query withdrawals for. Useful for templating
+    pub fn with_asset(self, asset: KAsset) -> Self {
+        self.update_input("asset", asset.to_string())
+    }
+
+    /// Filter withdrawals by the name of the withdrawal method
+    pub fn with_method(self, method: String) -> Self {
+        self.update_input("method", method)
+    }
+}
+
+impl MutateInput for KIWithdrawStatus {
+    fn list_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.params
+    }
+}
+
+impl UpdateInput for KIWithdrawStatus {}
+
+impl Input for KIWithdrawStatus {
+    type Output = KOWithdrawStatus;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
+        KrakenInput {
+            info: EndpointInfo {
+                methodtype: MethodType::Private,
+                endpoint: String::from("WithdrawStatus"),
+            },
+            params: Some(self.params),
+            output: PhantomData,
+        }
+    }
+
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
+        (
+            KrakenInput {
+                info: EndpointInfo {
+                    methodtype: MethodType::Private,
+                    endpoint: String::from("WithdrawStatus"),
+                },
+                params: Some(self.params.clone()),
+                output: PhantomData,
+            },
+            self,
+        )
+    }
+}
+
+/// Withdrawal status data | See [KOWithdrawStatus]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct KOWithdrawal {
+    /// Name of the withdrawal method used
+    pub method: String,
+    /// Asset class
+    pub aclass: String,
+    pub asset: KAsset,
+    /// Reference ID of the withdrawal
+    pub refid: String,
+    /// Method transaction ID
+    pub txid: Option<String>,
+    /// Method transaction information
+    pub info: Option<String>,
+    /// Amount withdrawn
+    pub amount: String,
+    /// Fees paid
+    pub fee: String,
+    /// Unix timestamp when the request was made
+    pub time: KTimestamp,
+    /// Status of the withdrawal: Initial, Pending, Settled, Success or Failure
+    pub status: String,
+    /// Additional status properties (if available):
+    /// + cancel-pending = cancelation requested
+    /// + canceled = canceled
+    /// + cancel-denied = cancelation requested but was denied
+    /// + return = a return transaction initiated by Kraken; it cannot be canceled
+    /// + onhold = withdrawal is on hold pending review
+    #[serde(rename = "status-prop")]
+    pub status_prop: Option<String>,
+}
+
+/// Response from the Get Status of Recent Withdrawals endpoint
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(transparent)]
+pub struct KOWithdrawStatus {
+    /// Recent withdrawals of the requested asset
+    pub withdrawals: Vec<KOWithdrawal>,
+}
+
+impl Output for KOWithdrawStatus {}
@@ -1,6 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, InputList, InputListItem, IntoInputList, KAsset, KrakenInput, MethodType,
@@ -8,10 +9,14 @@ use super::{
 };
 
 /// Request builder for the Get Asset Info endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIAssetInfo {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIAssetInfo, ["asset" => [KAsset]]);
+
 impl KIAssetInfo {
     /// Constructor returning a [KrakenInput] builder for the get asset info endpoint.
     pub fn build() -> Self {
@@ -42,17 +47,20 @@ impl KIAssetInfo {
 }
 
 impl Input for KIAssetInfo {
-    fn finish(self) -> KrakenInput {
+    type Output = KOAssetInfo;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Assets"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -60,6 +68,7 @@ impl Input for KIAssetInfo {
                     endpoint: String::from("Assets"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -1,6 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     AssetPairInfo, EndpointInfo, Input, InputList, InputListItem, IntoInputList, KAssetPair,
@@ -8,10 +9,14 @@ use super::{
 };
 
 /// Request builder for the Get Tradable Asset Pairs endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIAssetPairs {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIAssetPairs, ["pair" => [KAssetPair], "info" => AssetPairInfo]);
+
 impl KIAssetPairs {
     /// Constructor returning a [KrakenInput] builder for the get tradeable asset pairs endpoint.
     pub fn build() -> Self {
@@ -47,17 +52,20 @@ impl KIAssetPairs {
 }
 
 impl Input for KIAssetPairs {
-    fn finish(self) -> KrakenInput {
+    type Output = KOAssetPairInfo;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("AssetPairs"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -65,6 +73,7 @@ impl Input for KIAssetPairs {
                     endpoint: String::from("AssetPairs"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -1,17 +1,31 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, KAssetPair, KrakenInput, MethodType, MutateInput, OHLCInterval, Output,
     UpdateInput,
 };
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get OHLC Data endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIOHLC {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(
+    KIOHLC,
+    [
+        "pair" => KAssetPair,
+        "interval" => OHLCInterval,
+        "since" => String,
+    ],
+    required = ["pair"]
+);
+
 impl KIOHLC {
     /// Constructor returning a [KrakenInput] builder for the get OHLC data endpoint.
     /// * `pair` is the asset pair to query OHLC data for
@@ -41,17 +55,20 @@ impl KIOHLC {
 }
 
 impl Input for KIOHLC {
-    fn finish(self) -> KrakenInput {
+    type Output = KOOHLC;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("OHLC"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -59,6 +76,7 @@ impl Input for KIOHLC {
                     endpoint: String::from("OHLC"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -76,7 +94,7 @@ impl UpdateInput for KIOHLC {}
 /// OHLC info data
 #[derive(Deserialize, Serialize, Debug)]
 pub struct KOOHLCData {
-    pub timestamp: i64,
+    pub timestamp: KTimestamp,
     pub open: String,
     pub high: String,
     pub low: String,
@@ -1,16 +1,22 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, KAssetPair, KrakenInput, MethodType, MutateInput, Output, UpdateInput,
 };
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get Order Book endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIOrderBook {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIOrderBook, ["pair" => KAssetPair, "count" => i64], required = ["pair"]);
+
 impl KIOrderBook {
     /// Constructor returning a [KrakenInput] builder for the get order book endpoint.
     /// * `pair` is the asset pair to get market depth for
@@ -33,17 +39,20 @@ impl KIOrderBook {
 }
 
 impl Input for KIOrderBook {
-    fn finish(self) -> KrakenInput {
+    type Output = KOOrderBook;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Depth"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -51,6 +60,7 @@ impl Input for KIOrderBook {
                     endpoint: String::from("Depth"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -70,7 +80,7 @@ impl UpdateInput for KIOrderBook {}
 pub struct KOOrderBookData {
     pub price: String,
     pub volume: String,
-    pub timestamp: i64,
+    pub timestamp: KTimestamp,
 }
 
 /// Order book data
@@ -1,16 +1,22 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, KAssetPair, KrakenInput, MethodType, MutateInput, Output, UpdateInput,
 };
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get Recent Trades endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KIRecentTrades {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KIRecentTrades, ["pair" => KAssetPair, "since" => String], required = ["pair"]);
+
 impl KIRecentTrades {
     /// Constructor returning a [KrakenInput] builder for the get recent trades endpoint.
     /// * `pair` is the asset pair to query OHLC data for
@@ -34,17 +40,20 @@ impl KIRecentTrades {
 }
 
 impl Input for KIRecentTrades {
-    fn finish(self) -> KrakenInput {
+    type Output = KORecentTrades;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Trades"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -52,6 +61,7 @@ impl Input for KIRecentTrades {
                     endpoint: String::from("Trades"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -71,7 +81,7 @@ impl UpdateInput for KIRecentTrades {}
 pub struct KOTradeInfo {
     pub price: String,
     pub volume: String,
-    pub time: f64,
+    pub time: KTimestamp,
     pub tradetype: String,
     pub ordertype: String,
     pub misc: String,
@@ -1,31 +1,37 @@
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 use super::{EndpointInfo, Input, KrakenInput, MethodType, Output};
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get Server Time endpoint
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct KIServerTime();
 
 impl KIServerTime {
     /// Constructor returning a [KrakenInput] builder for the get server time endpoint.
     /// There are no inputs to this endpoint so finish() is called for you
-    pub fn build() -> KrakenInput {
+    pub fn build() -> KrakenInput<KOServerTime> {
         let time = KIServerTime();
         time.finish()
     }
 }
 
 impl Input for KIServerTime {
-    fn finish(self) -> KrakenInput {
+    type Output = KOServerTime;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Time"),
             },
             params: None,
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -33,6 +39,7 @@ impl Input for KIServerTime {
                     endpoint: String::from("Time"),
                 },
                 params: None,
+                output: PhantomData,
             },
             self,
         )
@@ -43,7 +50,7 @@ impl Input for KIServerTime {
 #[derive(Deserialize, Serialize, Debug)]
 pub struct KOServerTime {
     /// as unix timestamp
-    pub unixtime: u64,
+    pub unixtime: KTimestamp,
     /// as RFC 1123 time format
     pub rfc1123: String,
 }
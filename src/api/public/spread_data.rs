@@ -1,16 +1,22 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, KAssetPair, KrakenInput, MethodType, MutateInput, Output, UpdateInput,
 };
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get Recent Spread Data endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KISpreadData {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KISpreadData, ["pair" => KAssetPair, "since" => String], required = ["pair"]);
+
 impl KISpreadData {
     /// Constructor returning a [KrakenInput] builder for the get recent spread data endpoint.
     /// * `pair` is the asset pair to query OHLC data for
@@ -34,17 +40,20 @@ impl KISpreadData {
 }
 
 impl Input for KISpreadData {
-    fn finish(self) -> KrakenInput {
+    type Output = KOSpreadData;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Spread"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -52,6 +61,7 @@ impl Input for KISpreadData {
                     endpoint: String::from("Spread"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
@@ -69,7 +79,7 @@ impl UpdateInput for KISpreadData {}
 /// Spread info
 #[derive(Deserialize, Serialize, Debug)]
 pub struct KOSpreadInfo {
-    pub time: i64,
+    pub time: KTimestamp,
     pub bid: String,
     pub ask: String,
 }
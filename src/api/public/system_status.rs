@@ -1,31 +1,37 @@
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
 use super::{EndpointInfo, Input, KrakenInput, MethodType, Output, SystemStatus};
+use crate::api::timestamp::KTimestamp;
 
 /// Request builder for the Get System Status endpoint
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct KISystemStatus();
 
 impl KISystemStatus {
     /// Constructor returning a [KrakenInput] builder for the get server time endpoint.
     /// There are no inputs to this endpoint so finish() is called for you
-    pub fn build() -> KrakenInput {
+    pub fn build() -> KrakenInput<KOSystemStatus> {
         let status = KISystemStatus();
         status.finish()
     }
 }
 
 impl Input for KISystemStatus {
-    fn finish(self) -> KrakenInput {
+    type Output = KOSystemStatus;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("SystemStatus"),
             },
             params: None,
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -33,6 +39,7 @@ impl Input for KISystemStatus {
                     endpoint: String::from("SystemStatus"),
                 },
                 params: None,
+                output: PhantomData,
             },
             self,
         )
@@ -45,7 +52,7 @@ pub struct KOSystemStatus {
     /// Current system status or trading mode
     pub status: SystemStatus,
     /// Server time
-    pub timestamp: String,
+    pub timestamp: KTimestamp,
 }
 
 impl Output for KOSystemStatus {}
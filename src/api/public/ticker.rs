@@ -1,6 +1,7 @@
 use indexmap::map::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use super::{
     EndpointInfo, Input, InputList, InputListItem, IntoInputList, KAssetPair, KrakenInput,
@@ -8,10 +9,14 @@ use super::{
 };
 
 /// Request builder for the Get Ticker Information endpoint
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct KITicker {
     params: IndexMap<String, String>,
 }
 
+deserialize_params!(KITicker, ["pair" => [KAssetPair]], required = ["pair"]);
+
 impl KITicker {
     /// Constructor returning a [KrakenInput] builder for the get ticker information endpoint.
     /// * `pair` is the asset pair to query info for
@@ -46,17 +51,20 @@ impl KITicker {
 }
 
 impl Input for KITicker {
-    fn finish(self) -> KrakenInput {
+    type Output = KOTicker;
+
+    fn finish(self) -> KrakenInput<Self::Output> {
         KrakenInput {
             info: EndpointInfo {
                 methodtype: MethodType::Public,
                 endpoint: String::from("Ticker"),
             },
             params: Some(self.params),
+            output: PhantomData,
         }
     }
 
-    fn finish_clone(self) -> (KrakenInput, Self) {
+    fn finish_clone(self) -> (KrakenInput<Self::Output>, Self) {
         (
             KrakenInput {
                 info: EndpointInfo {
@@ -64,6 +72,7 @@ impl Input for KITicker {
                     endpoint: String::from("Ticker"),
                 },
                 params: Some(self.params.clone()),
+                output: PhantomData,
             },
             self,
         )
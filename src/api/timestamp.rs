@@ -0,0 +1,394 @@
+//! Module containing the timestamp type used by input builders and outputs
+//!
+//! Kraken sends times as Unix seconds, either whole or with a fractional part, as RFC3339
+//! strings or, in export reports, as `YYYY-MM-DD hh:mm:ss.ffff` in UTC. Every output field
+//! holding a time is a [KTimestamp], which accepts all of these and keeps the sub-second part
+//!
+//! A [KTimestamp] converts to and from [SystemTime], which date and time libraries such as
+//! `chrono` and `time` can convert from
+//!
+//! ```
+//! # use kraapi::api::timestamp::KTimestamp;
+//! # use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//! let opentm: KTimestamp = "1688669448.1234".parse().unwrap();
+//! assert_eq!(opentm.unix_seconds(), 1688669448);
+//! assert_eq!(opentm.subsec_nanos(), 123_400_000);
+//! assert_eq!(
+//!     SystemTime::from(opentm),
+//!     UNIX_EPOCH + Duration::new(1688669448, 123_400_000)
+//! );
+//! ```
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{KError, KrakenErrors};
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// Point in time as Unix seconds and nanoseconds, in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KTimestamp {
+    secs: i64,
+    nanos: u32,
+}
+
+impl KTimestamp {
+    /// Construct a KTimestamp from whole Unix seconds and the nanoseconds past them. Nanoseconds
+    /// above a second carry into the seconds
+    pub fn from_unix(secs: i64, nanos: u32) -> Self {
+        KTimestamp {
+            secs: secs + i64::from(nanos) / NANOS_PER_SEC,
+            nanos: (i64::from(nanos) % NANOS_PER_SEC) as u32,
+        }
+    }
+
+    /// The current time
+    pub fn now() -> Self {
+        KTimestamp::from(SystemTime::now())
+    }
+
+    /// Whole Unix seconds, rounded towards negative infinity
+    pub fn unix_seconds(&self) -> i64 {
+        self.secs
+    }
+
+    /// Nanoseconds past [unix_seconds()][KTimestamp::unix_seconds]
+    pub fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Unix seconds as a float, as most of Kraken's endpoints send them
+    pub fn as_secs_f64(&self) -> f64 {
+        self.secs as f64 + f64::from(self.nanos) / NANOS_PER_SEC as f64
+    }
+
+    // Kraken sends floats with up to microsecond precision. Rounding to it drops the error of
+    // the float representation
+    fn from_secs_f64(secs: f64) -> Option<Self> {
+        if !secs.is_finite() {
+            return None;
+        }
+        let whole = secs.floor();
+        let micros = ((secs - whole) * 1e6).round() as i64;
+        Some(KTimestamp::from_unix(whole as i64, (micros * 1_000) as u32))
+    }
+
+    // Decimal Unix seconds, keeping every written digit of the fraction up to nanoseconds
+    fn parse_unix(time: &str) -> Option<Self> {
+        let (secs, fraction) = match time.split_once('.') {
+            Some((secs, fraction)) => (secs, fraction),
+            None => (time, ""),
+        };
+        if !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let secs = secs.parse::<i64>().ok()?;
+        let nanos = parse_fraction(fraction)?;
+        if time.starts_with('-') && nanos > 0 {
+            Some(KTimestamp::from_unix(
+                secs.checked_sub(1)?,
+                (NANOS_PER_SEC as u32) - nanos,
+            ))
+        } else {
+            Some(KTimestamp::from_unix(secs, nanos))
+        }
+    }
+
+    // RFC3339 such as "2023-07-06T18:52:00.123Z" or "2023-07-06T20:52:00+02:00". A space is
+    // accepted in place of the "T" and a missing offset is taken as UTC, which covers the
+    // "YYYY-MM-DD hh:mm:ss.ffff" format of export reports
+    fn parse_datetime(time: &str) -> Option<Self> {
+        if time.len() < 19 || !time.is_char_boundary(10) || !time.is_char_boundary(19) {
+            return None;
+        }
+        let (date, rest) = time.split_at(10);
+        let rest = rest.strip_prefix(|c| matches!(c, 'T' | 't' | ' '))?;
+        let (clock, rest) = rest.split_at(8);
+
+        let mut date = date.splitn(3, '-').map(parse_digits);
+        let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+        let mut clock = clock.splitn(3, ':').map(parse_digits);
+        let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+        // A second of 60 is a leap second, which is counted as the first second of the next
+        // minute
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return None;
+        }
+
+        let (fraction, offset) = match rest.strip_prefix('.') {
+            Some(rest) => {
+                let digits = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                rest.split_at(digits)
+            }
+            None => ("", rest),
+        };
+        let offset = match offset {
+            "" | "Z" | "z" => 0,
+            offset => {
+                let (sign, offset) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+                    (Some(offset), _) => (1, offset),
+                    (_, Some(offset)) => (-1, offset),
+                    _ => return None,
+                };
+                let (hours, minutes) = offset.split_once(':')?;
+                sign * (parse_digits(hours)? * 3_600 + parse_digits(minutes)? * 60)
+            }
+        };
+
+        let secs = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second
+            - offset;
+        Some(KTimestamp::from_unix(secs, parse_fraction(fraction)?))
+    }
+
+    // Whole seconds as sent in request parameters
+    pub(crate) fn to_param(self) -> String {
+        self.secs.to_string()
+    }
+}
+
+// Unsigned decimal number of up to 4 digits, as found in the fields of a date and time
+fn parse_digits(digits: &str) -> Option<i64> {
+    match !digits.is_empty() && digits.len() <= 4 && digits.bytes().all(|b| b.is_ascii_digit()) {
+        true => digits.parse().ok(),
+        false => None,
+    }
+}
+
+// Nanoseconds written as the digits after the decimal point, dropping digits past nanoseconds
+fn parse_fraction(fraction: &str) -> Option<u32> {
+    let digits = &fraction[..fraction.len().min(9)];
+    if digits.is_empty() {
+        return Some(0);
+    }
+    let nanos = digits.parse::<u32>().ok()?;
+    Some(nanos * 10u32.pow(9 - digits.len() as u32))
+}
+
+// Days since the Unix epoch for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Proleptic Gregorian date of a number of days since the Unix epoch
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+impl From<SystemTime> for KTimestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => KTimestamp::from_unix(since.as_secs() as i64, since.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                match before.subsec_nanos() {
+                    0 => KTimestamp::from_unix(-(before.as_secs() as i64), 0),
+                    nanos => KTimestamp::from_unix(
+                        -(before.as_secs() as i64) - 1,
+                        NANOS_PER_SEC as u32 - nanos,
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl From<KTimestamp> for SystemTime {
+    fn from(time: KTimestamp) -> Self {
+        if time.secs >= 0 {
+            UNIX_EPOCH + Duration::new(time.secs as u64, time.nanos)
+        } else {
+            UNIX_EPOCH - Duration::from_secs(time.secs.unsigned_abs())
+                + Duration::from_nanos(u64::from(time.nanos))
+        }
+    }
+}
+
+/// Whole Unix seconds
+impl From<u64> for KTimestamp {
+    fn from(secs: u64) -> Self {
+        KTimestamp::from_unix(secs as i64, 0)
+    }
+}
+
+impl FromStr for KTimestamp {
+    type Err = KrakenErrors<KError>;
+
+    /// Parse decimal Unix seconds or an RFC3339 date and time
+    fn from_str(time: &str) -> Result<Self, Self::Err> {
+        let time = time.trim();
+        KTimestamp::parse_unix(time)
+            .or_else(|| KTimestamp::parse_datetime(time))
+            .ok_or_else(|| KrakenErrors(vec![KError::TimestampParseError]))
+    }
+}
+
+/// Formats as RFC3339 in UTC, such as `2023-07-06T18:52:00.1234Z`
+impl fmt::Display for KTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.secs.div_euclid(86_400));
+        let secs = self.secs.rem_euclid(86_400);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            secs / 3_600,
+            secs % 3_600 / 60,
+            secs % 60
+        )?;
+        if self.nanos > 0 {
+            let fraction = format!("{:09}", self.nanos);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        write!(f, "Z")
+    }
+}
+
+/// Serializes as Unix seconds, with a fractional part if there is one
+impl Serialize for KTimestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.nanos {
+            0 => serializer.serialize_i64(self.secs),
+            _ => serializer.serialize_f64(self.as_secs_f64()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KTimestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TimestampVisitor;
+
+        impl<'de> Visitor<'de> for TimestampVisitor {
+            type Value = KTimestamp;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Unix seconds or an RFC3339 date and time")
+            }
+
+            fn visit_i64<E: de::Error>(self, secs: i64) -> Result<Self::Value, E> {
+                Ok(KTimestamp::from_unix(secs, 0))
+            }
+
+            fn visit_u64<E: de::Error>(self, secs: u64) -> Result<Self::Value, E> {
+                i64::try_from(secs)
+                    .map(|secs| KTimestamp::from_unix(secs, 0))
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(secs), &self))
+            }
+
+            fn visit_f64<E: de::Error>(self, secs: f64) -> Result<Self::Value, E> {
+                KTimestamp::from_secs_f64(secs)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Float(secs), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, time: &str) -> Result<Self::Value, E> {
+                time.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(time), &self))
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamps() {
+        let time = |time: &str| time.parse::<KTimestamp>().ok();
+        assert_eq!(time("0"), Some(KTimestamp::from_unix(0, 0)));
+        assert_eq!(
+            time("1616669193.9817"),
+            Some(KTimestamp::from_unix(1616669193, 981_700_000))
+        );
+        assert_eq!(time("-1.25"), Some(KTimestamp::from_unix(-2, 750_000_000)));
+        assert_eq!(
+            time("2021-03-25 10:46:33.9817"),
+            Some(KTimestamp::from_unix(1616669193, 981_700_000))
+        );
+        assert_eq!(
+            time("2023-07-06T18:52:00Z"),
+            Some(KTimestamp::from_unix(1688669520, 0))
+        );
+        assert_eq!(
+            time("2023-07-06T20:52:00.000001+02:00"),
+            Some(KTimestamp::from_unix(1688669520, 1_000))
+        );
+        assert_eq!(
+            time("1970-01-01 00:00:00"),
+            Some(KTimestamp::from_unix(0, 0))
+        );
+        assert_eq!(time("not a time"), None);
+        assert_eq!(time("2023-13-06T18:52:00Z"), None);
+        assert_eq!(time("2023-07-06T18:52:61Z"), None);
+        assert_eq!(time("2023-07-06T18:+2:00Z"), None);
+        assert_eq!(time("-9223372036854775808.5"), None);
+
+        let time: KTimestamp = serde_json::from_str("1688669448.1234").unwrap();
+        assert_eq!(time, KTimestamp::from_unix(1688669448, 123_400_000));
+        assert_eq!(time.to_string(), "2023-07-06T18:50:48.1234Z");
+        assert_eq!(serde_json::to_string(&time).unwrap(), "1688669448.1234");
+        assert_eq!(KTimestamp::from(SystemTime::from(time)), time);
+        let time: KTimestamp = serde_json::from_str("\"2023-07-06T18:52:00Z\"").unwrap();
+        assert_eq!(serde_json::to_string(&time).unwrap(), "1688669520");
+    }
+
+    #[test]
+    fn malformed_timestamps() {
+        // Replace each character of valid timestamps with multi-byte characters, and cut them
+        // short at every character, which must be rejected rather than panic
+        let valid = [
+            "2023-07-06T20:52:00.000001+02:00",
+            "2021-03-25 10:46:33.9817",
+            "-1616669193.9817",
+        ];
+        for time in valid {
+            for (index, _) in time.char_indices() {
+                for replacement in ["é", "€", "😀", "+", "-", ":", "."] {
+                    let mut malformed = String::from(&time[..index]);
+                    malformed.push_str(replacement);
+                    malformed.push_str(&time[index + 1..]);
+                    let _ = malformed.parse::<KTimestamp>();
+                    let _ = format!("{}{}", &time[..index], replacement).parse::<KTimestamp>();
+                }
+            }
+        }
+        assert!("2023-07-06é1:00:00".parse::<KTimestamp>().is_err());
+        assert!("2023-07-06T21:00:00é".parse::<KTimestamp>().is_err());
+    }
+}
@@ -1,55 +1,87 @@
-use hmac::{Hmac, Mac, NewMac};
-use sha2::{Digest, Sha256, Sha512};
-use std::time::SystemTime;
+use std::io;
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
 
-type HmacSha512 = Hmac<Sha512>;
+use crate::credentials::{Credentials, Secret};
+use crate::error::{KError, KrakenErrors};
+use crate::nonce::NonceSource;
+use crate::signer::{HmacSigner, Signer};
+use crate::trace;
 
 pub(crate) struct KrakenAuth {
-    api_key: String,
-    api_secret: String,
+    key: Secret,
+    signer: Arc<dyn Signer>,
 }
 
 impl KrakenAuth {
     pub(crate) fn new(key: &str, secret: &str) -> Self {
+        KrakenAuth::from_credentials(Credentials::new(key, secret))
+    }
+
+    pub(crate) fn from_credentials(credentials: Credentials) -> Self {
         KrakenAuth {
-            api_key: key.to_string(),
-            api_secret: secret.to_string(),
+            key: credentials.key().clone(),
+            signer: Arc::new(HmacSigner::new(credentials.secret().clone())),
         }
     }
 
-    pub(crate) fn key(&self) -> &String {
-        &self.api_key
+    pub(crate) fn set_signer(&mut self, signer: Arc<dyn Signer>) {
+        self.signer = signer;
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn secret(&self) -> &String {
-        &self.api_secret
+    pub(crate) fn key(&self) -> &str {
+        self.key.expose()
     }
 
-    pub(crate) fn nonce() -> String {
-        let duration = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap();
-        let nonce = (duration.as_secs() * 1_000_000u64) + u64::from(duration.subsec_micros());
-
-        (nonce as u64).to_string()
+    // Check that the credentials can be used to sign requests without sending anything to Kraken
+    pub(crate) fn validate(&self) -> Result<(), KrakenErrors<KError>> {
+        if self.key().is_empty() || http::HeaderValue::from_str(self.key()).is_err() {
+            return Err(KrakenErrors(vec![KError::MalformedKey]));
+        }
+        self.signer.validate()
     }
 
-    pub(crate) fn sign(&self, path: &str, nonce: &str, params: &str) -> String {
-        let api_secret = base64::decode(&self.api_secret).expect("Invalid API secret");
-        // Use base64 decoded API key as the HMAC key with Sha512 as the hashing function
-        let mut hmac = HmacSha512::new_from_slice(&api_secret).expect("Invalid API secret length");
-        let mut sha256 = Sha256::new();
+    // Signers may block, e.g. waiting on a signing daemon, so signing runs on tokio's blocking
+    // thread pool rather than holding up the runtime
+    pub(crate) async fn sign(
+        &self,
+        path: &str,
+        nonce: &str,
+        params: &str,
+    ) -> Result<String, KrakenErrors<KError>> {
+        let signer = Arc::clone(&self.signer);
+        let (path, nonce, params) = (path.to_string(), nonce.to_string(), params.to_string());
+        tokio::task::spawn_blocking(move || signer.sign(&path, &nonce, &params))
+            .await
+            .map_err(|err| KrakenErrors(vec![KError::SignerError(err.into())]))?
+    }
+}
 
-        // SHA256(nonce + POST data)
-        sha256.update(nonce.as_bytes());
-        sha256.update(params.as_bytes());
-        let sha_res = sha256.finalize();
+// Issues nonces from the client's NonceSource. Owned by the client so that nonces are assigned
+// when a request is dispatched rather than when its input is built, which lets a KrakenInput be
+// reused and built well ahead of being sent. Kraken rejects a nonce smaller than one it already
+// accepted, so a request holds the generator from being issued its nonce until Kraken has
+// answered it, keeping concurrent requests in nonce order on the wire
+pub(crate) struct NonceGenerator {
+    source: Box<dyn NonceSource>,
+    order: Mutex<()>,
+}
 
-        hmac.update(path.as_bytes());
-        hmac.update(&sha_res);
+impl NonceGenerator {
+    pub(crate) fn new<S: NonceSource + 'static>(source: S) -> Self {
+        NonceGenerator {
+            source: Box::new(source),
+            order: Mutex::new(()),
+        }
+    }
 
-        base64::encode(hmac.finalize().into_bytes())
+    // Lock the generator and issue the next nonce. No other nonce is issued until the returned
+    // guard is dropped, which must happen only once the request carrying the nonce is answered
+    pub(crate) async fn next(&self) -> io::Result<(MutexGuard<'_, ()>, u64)> {
+        let order = self.order.lock().await;
+        let nonce = self.source.next_nonce()?;
+        trace::log_trace!(nonce, "issued nonce");
+        Ok((order, nonce))
     }
 }
 
@@ -58,10 +90,11 @@ mod tests {
 
     use super::*;
     use crate::api;
+    use crate::nonce::AtomicNonce;
     use indexmap::map::IndexMap;
 
-    #[test]
-    fn test_auth() {
+    #[tokio::test]
+    async fn test_auth() {
         let auth = KrakenAuth::new("CJbfPw4tnbf/9en/ZmpewCTKEwmmzO18LXZcHQcu7HPLWre4l8+V9I3y",
             "FRs+gtq09rR7OFtKj9BGhyOGS3u5vtY/EdiIBO9kD8NFtRX7w7LeJDSrX6cq1D8zmQmGkWFjksuhBvKOAWJohQ==");
         let api_path = String::from("/0/private/TradeBalance");
@@ -69,10 +102,39 @@ mod tests {
         let mut params = IndexMap::new();
         params.insert("nonce".to_string(), api_nonce.clone());
         params.insert("asset".to_string(), "xbt".to_string());
-        let params = api::format_params(&Some(&params)).unwrap();
+        let params = api::format_params(&Some(&params), &[]).unwrap();
 
-        let signature = auth.sign(&api_path, &api_nonce, &params);
+        let signature = auth.sign(&api_path, &api_nonce, &params).await.unwrap();
 
         assert_eq!(signature, String::from("RdQzoXRC83TPmbERpFj0XFVArq0Hfadm0eLolmXTuN2R24hzIqtAnF/f7vSfW1tGt7xQOn8bjm+Ht+X0KrMwlA=="));
     }
+
+    #[tokio::test]
+    async fn malformed_credentials() {
+        let secret = "FRs+gtq09rR7OFtKj9BGhyOGS3u5vtY/EdiIBO9kD8NFtRX7w7LeJDSrX6cq1D8zmQmGkWFjksuhBvKOAWJohQ==";
+        assert!(KrakenAuth::new("key", secret).validate().is_ok());
+        assert!(matches!(
+            KrakenAuth::new("", secret).validate().unwrap_err().0[..],
+            [KError::MalformedKey]
+        ));
+        assert!(matches!(
+            KrakenAuth::new("key\n", secret).validate().unwrap_err().0[..],
+            [KError::MalformedKey]
+        ));
+        assert!(matches!(
+            KrakenAuth::new("key", "not base64!").sign("/0/private/Balance", "1", "nonce=1").await,
+            Err(KrakenErrors(ref errors)) if matches!(errors[..], [KError::MalformedSecret])
+        ));
+    }
+
+    #[tokio::test]
+    async fn increasing_nonces() {
+        let generator = NonceGenerator::new(AtomicNonce::new());
+        let mut nonces = Vec::new();
+        for _ in 0..1000 {
+            nonces.push(generator.next().await.unwrap().1);
+        }
+
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 }
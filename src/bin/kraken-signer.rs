@@ -0,0 +1,72 @@
+//! Reference signing daemon for [UnixSocketSigner][kraapi::signer::UnixSocketSigner]
+//!
+//! Reads the base64 encoded API secret from the `KRAKEN_API_SECRET` environment variable and
+//! answers signing requests on the Unix socket at the path given as the only argument. The socket
+//! is made accessible only to the user running the daemon
+//!
+//! ```text
+//! KRAKEN_API_SECRET=... kraken-signer /run/kraken-signer.sock
+//! ```
+//!
+//! Intended for testing and as a starting point. A production daemon would typically add access
+//! control, auditing and rate limiting around [serve()][kraapi::signer::serve]
+
+#[cfg(unix)]
+fn main() {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+    use std::path::Path;
+    use std::{env, fs, process};
+
+    use kraapi::credentials::Secret;
+    use kraapi::signer::{self, HmacSigner, Signer};
+
+    let fail = |message: String| -> ! {
+        eprintln!("kraken-signer: {}", message);
+        process::exit(1);
+    };
+
+    let path = match (env::args().nth(1), env::args().nth(2)) {
+        (Some(path), None) => path,
+        _ => fail(String::from("usage: kraken-signer <socket path>")),
+    };
+    let secret = env::var("KRAKEN_API_SECRET")
+        .map(Secret::new)
+        .unwrap_or_else(|_| fail(String::from("KRAKEN_API_SECRET is not set")));
+    env::remove_var("KRAKEN_API_SECRET");
+
+    let signer = HmacSigner::new(secret);
+    if let Err(err) = signer.validate() {
+        fail(err.to_string());
+    }
+
+    // The socket is bound inside a directory only accessible to the user running the daemon and
+    // only linked at `path` once its own permissions are restricted, so no other user can
+    // connect in between. Linking fails rather than replacing anything already at `path`
+    let private = format!("{}.{}.tmp", path, process::id());
+    let bound = Path::new(&private).join("socket");
+    let listener = fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private)
+        .and_then(|_| UnixListener::bind(&bound))
+        .and_then(|listener| {
+            fs::set_permissions(&bound, fs::Permissions::from_mode(0o600))?;
+            fs::hard_link(&bound, &path)?;
+            Ok(listener)
+        });
+    let _ = fs::remove_file(&bound);
+    let _ = fs::remove_dir(&private);
+    let listener = listener.unwrap_or_else(|err| fail(format!("failed to bind {}: {}", path, err)));
+
+    let res = signer::serve(&listener, &signer);
+    let _ = fs::remove_file(&path);
+    if let Err(err) = res {
+        fail(err.to_string());
+    }
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("kraken-signer: Unix sockets are not supported on this platform");
+    std::process::exit(1);
+}
@@ -0,0 +1,171 @@
+//! Synchronous client for programs that don't run an async runtime. Requires the `blocking`
+//! feature
+//!
+//! [KrakenBlockingClient] wraps a [KrakenClient] together with a single threaded runtime of its
+//! own and blocks the calling thread until each request completes. It takes the same input
+//! builders and returns the same output types as the asynchronous client
+//!
+//! ```no_run
+//! # use kraapi::blocking::KrakenBlockingClient;
+//! # use kraapi::public::ticker::KITicker;
+//! # use kraapi::api::asset::{KAsset, KAssetPair};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = KrakenBlockingClient::new("", "");
+//!
+//! let ticker = client.send(KITicker::build(KAssetPair(KAsset::XBT, KAsset::USD)))?;
+//!
+//! println!("{:#?}", ticker);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Panics
+//!
+//! The requests of a [KrakenBlockingClient] panic when made from within an async runtime, as
+//! blocking there would stall the runtime. Use [KrakenClient] in async code
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::api::{Endpoint, KrakenInput, KrakenResult, Output};
+use crate::client::KrakenClient;
+use crate::credentials::CredentialProvider;
+use crate::error::KWarning;
+use crate::private::retrieve_export::KORetrieveExport;
+
+/// Synchronous HTTP client implementation sending instances of [KrakenInput] to the Kraken
+/// servers
+pub struct KrakenBlockingClient {
+    client: KrakenClient,
+    runtime: Runtime,
+}
+
+impl KrakenBlockingClient {
+    /// Construct a new KrakenBlockingClient instance. See [KrakenClient::new]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime can't be started
+    pub fn new(key: &str, secret: &str) -> Self {
+        KrakenBlockingClient::from(KrakenClient::new(key, secret))
+    }
+
+    /// Construct a new KrakenBlockingClient instance, checking the credentials. See
+    /// [KrakenClient::try_new]
+    pub fn try_new(key: &str, secret: &str) -> KrakenResult<Self> {
+        KrakenClient::try_new(key, secret).map(KrakenBlockingClient::from)
+    }
+
+    /// Construct a new KrakenBlockingClient instance from credentials loaded by a
+    /// [CredentialProvider]. See [KrakenClient::from_provider]
+    pub fn from_provider<P: CredentialProvider>(provider: &P) -> KrakenResult<Self> {
+        KrakenClient::from_provider(provider).map(KrakenBlockingClient::from)
+    }
+
+    /// Construct a new KrakenBlockingClient instance that is allowed to send requests to the
+    /// withdrawal endpoints. See [KrakenClient::new_with_withdrawals]
+    pub fn new_with_withdrawals(key: &str, secret: &str) -> Self {
+        KrakenBlockingClient::from(KrakenClient::new_with_withdrawals(key, secret))
+    }
+
+    /// The wrapped asynchronous client
+    pub fn client(&self) -> &KrakenClient {
+        &self.client
+    }
+
+    /// Mutable access to the wrapped asynchronous client, to change its settings such as the
+    /// [signer][KrakenClient::set_signer] or [two factor password][KrakenClient::set_two_factor]
+    pub fn client_mut(&mut self) -> &mut KrakenClient {
+        &mut self.client
+    }
+
+    /// Make a request to the desired API endpoint by passing a fully constructed [KrakenInput].
+    /// See [KrakenClient::request]
+    pub fn request<T>(&self, input: &KrakenInput<T>) -> KrakenResult<T>
+    where
+        T: Output + DeserializeOwned,
+    {
+        self.runtime.block_on(self.client.request(input))
+    }
+
+    /// Make a request, also returning the warnings Kraken sent alongside the result. See
+    /// [KrakenClient::request_with_warnings]
+    pub fn request_with_warnings<T>(
+        &self,
+        input: &KrakenInput<T>,
+    ) -> KrakenResult<(T, Vec<KWarning>)>
+    where
+        T: Output + DeserializeOwned,
+    {
+        self.runtime
+            .block_on(self.client.request_with_warnings(input))
+    }
+
+    /// Send an input builder or a finished [KrakenInput] to its endpoint. See
+    /// [KrakenClient::send]
+    pub fn send<E>(&self, endpoint: E) -> KrakenResult<E::Output>
+    where
+        E: Endpoint,
+        E::Output: DeserializeOwned,
+    {
+        self.runtime.block_on(self.client.send(endpoint))
+    }
+
+    /// Make a request, returning the result as untyped json. See
+    /// [KrakenClient::request_value]
+    pub fn request_value<O>(&self, input: &KrakenInput<O>) -> KrakenResult<Value> {
+        self.runtime.block_on(self.client.request_value(input))
+    }
+
+    /// Retrieve a processed export report. See [KrakenClient::request_export]
+    pub fn request_export(
+        &self,
+        input: &KrakenInput<KORetrieveExport>,
+    ) -> KrakenResult<KORetrieveExport> {
+        self.runtime.block_on(self.client.request_export(input))
+    }
+}
+
+impl From<KrakenClient> for KrakenBlockingClient {
+    /// Wrap an existing client, keeping all of its settings
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime can't be started
+    fn from(client: KrakenClient) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the runtime of the blocking client");
+        KrakenBlockingClient { client, runtime }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use crate::public::server_time::KIServerTime;
+
+    #[test]
+    fn blocking_request() {
+        let server = Runtime::new().unwrap();
+        let url = server.block_on(mock::serve(|_| {
+            (
+                200,
+                "application/json",
+                String::from(r#"{"error":[],"result":{"unixtime":1688669448,"rfc1123":"Thu"}}"#),
+            )
+        }));
+
+        let mut client = KrakenBlockingClient::new("", "");
+        client.client_mut().set_url(&url);
+        let time = client.send(KIServerTime::build()).unwrap();
+        assert_eq!(time.unixtime.unix_seconds(), 1688669448);
+        assert_eq!(
+            client.request_value(&KIServerTime::build()).unwrap()["rfc1123"],
+            "Thu"
+        );
+    }
+}
@@ -1,24 +1,44 @@
 //! Asynchronous HTTP client implementation sending instances of [KrakenInput] to the Kraken servers
-use hyper::body;
-use hyper::client::HttpConnector;
-use hyper::header::{CONTENT_TYPE, USER_AGENT};
-use hyper::{Body, Client, Request};
-use hyper_tls::HttpsConnector;
-use serde::de::DeserializeOwned;
-
-use super::auth::KrakenAuth;
-use super::error;
+use hyper::body::{self, Bytes};
+use hyper::header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT};
+use hyper::{Body, Client, Method, Request, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde_json::error::Category;
+use serde_json::Value;
+use std::io;
+use std::sync::Arc;
+use std::time::Instant;
+use zip::result::ZipError;
+
+use super::auth::{KrakenAuth, NonceGenerator};
+use super::connector::Connector;
+use super::error::{self, KErrorContext, KWarning};
 use crate::api;
-use crate::api::{KResult, KrakenInput, KrakenResult, MethodType, Output};
+use crate::api::{Endpoint, KResult, KrakenInput, KrakenResult, MethodType, Output};
+use crate::credentials::{CredentialProvider, Credentials};
+use crate::metrics::KrakenMetrics;
+use crate::middleware::{Middleware, RawResponse, SignedRequest, UnsignedRequest};
+use crate::nonce::{AtomicNonce, NonceSource};
+use crate::otp::TwoFactor;
+use crate::private::retrieve_export::KORetrieveExport;
+use crate::proxy::Proxy;
+use crate::signer::Signer;
+use crate::trace::{self, RequestSpan};
 
-type HttpClient = Box<hyper::Client<HttpsConnector<HttpConnector>, hyper::Body>>;
+type HttpClient = Box<hyper::Client<Connector, hyper::Body>>;
 
 /// Asynchronous HTTP client implementation sending instances of [KrakenInput] to the Kraken servers
 pub struct KrakenClient {
     url: String,
     version: String,
     auth: KrakenAuth,
-    client: HttpClient,
+    nonce: NonceGenerator,
+    two_factor: Option<TwoFactor>,
+    // Fails if the TLS backend couldn't be initialized
+    client: io::Result<HttpClient>,
+    withdrawals: bool,
+    middleware: Vec<Box<dyn Middleware>>,
+    metrics: Option<KrakenMetrics>,
 }
 
 impl KrakenClient {
@@ -30,21 +50,69 @@ impl KrakenClient {
     /// ## Note
     ///
     /// If only calling public endpoints, passing empty string literals for key and secret is
-    /// acceptable. However, trying to call a private endpoint with empty credentials will panic.
+    /// acceptable. Trying to call a private endpoint with empty or malformed credentials will
+    /// return [MalformedKey][error::KError::MalformedKey] or
+    /// [MalformedSecret][error::KError::MalformedSecret] without sending the request. Use
+    /// [try_new()][KrakenClient::try_new] to check the credentials and the TLS backend up front
+    ///
     /// If needing to call both public and private endpoints, a single authenticated client will
     /// suffice but unique clients can be used as well
     pub fn new(key: &str, secret: &str) -> Self {
-        let https = HttpsConnector::new();
         KrakenClient {
             url: String::from("https://api.kraken.com"),
             version: String::from("0"),
             auth: KrakenAuth::new(&key, &secret),
-            client: Box::new(
-                Client::builder()
-                    .pool_idle_timeout(None)
-                    .http1_title_case_headers(true)
-                    .build::<_, hyper::Body>(https),
-            ),
+            nonce: NonceGenerator::new(AtomicNonce::new()),
+            two_factor: None,
+            client: http_client(None),
+            withdrawals: false,
+            middleware: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    /// Construct a new KrakenClient instance, checking that the credentials can be used to sign
+    /// requests to private endpoints
+    ///
+    /// Returns [MalformedKey][error::KError::MalformedKey] if the key is empty or can't be sent in
+    /// an http header and [MalformedSecret][error::KError::MalformedSecret] if the secret is empty
+    /// or not valid base64. Nothing is sent to Kraken, so a well formed key that Kraken doesn't
+    /// recognise is only reported once a request is made. Returns
+    /// [TlsError][error::KError::TlsError] if the TLS backend couldn't be initialized, which
+    /// [new()][KrakenClient::new] only reports when a request is made
+    pub fn try_new(key: &str, secret: &str) -> KrakenResult<Self> {
+        let client = KrakenClient::new(key, secret);
+        client.http()?;
+        client.auth().validate()?;
+        Ok(client)
+    }
+
+    /// Construct a new KrakenClient instance from credentials loaded by a [CredentialProvider]
+    ///
+    /// The credentials are checked as they are in [try_new()][KrakenClient::try_new]
+    pub fn from_provider<P: CredentialProvider>(provider: &P) -> KrakenResult<Self> {
+        let client = KrakenClient {
+            auth: KrakenAuth::from_credentials(provider.credentials()?),
+            ..KrakenClient::new("", "")
+        };
+        client.http()?;
+        client.auth().validate()?;
+        Ok(client)
+    }
+
+    /// Construct a new KrakenClient instance that is allowed to send requests to the withdrawal
+    /// endpoints
+    ///
+    /// ## Note
+    ///
+    /// Clients constructed with [new()][KrakenClient::new] refuse to send any withdrawal request
+    /// and return [WithdrawalsDisabled][error::KError::WithdrawalsDisabled] instead. Withdrawals
+    /// can only be enabled here so that moving funds off the exchange is always a deliberate
+    /// decision made when the client is created
+    pub fn new_with_withdrawals(key: &str, secret: &str) -> Self {
+        KrakenClient {
+            withdrawals: true,
+            ..KrakenClient::new(key, secret)
         }
     }
 
@@ -64,10 +132,84 @@ impl KrakenClient {
     }
 
     /// Assign new credentials for this KrakenClient
+    ///
+    /// Requests are then signed in process with an [HmacSigner][crate::signer::HmacSigner] using
+    /// the new secret, replacing any signer set with [set_signer()][KrakenClient::set_signer]
     pub fn set_auth(&mut self, key: &str, secret: &str) {
         self.auth = KrakenAuth::new(&key, &secret);
     }
 
+    /// Assign new credentials for this KrakenClient, keeping them in [Secret]s
+    ///
+    /// [Secret]: crate::credentials::Secret
+    pub fn set_credentials(&mut self, credentials: Credentials) {
+        self.auth = KrakenAuth::from_credentials(credentials);
+    }
+
+    /// Replace the source of nonces used to sign requests to private endpoints
+    ///
+    /// Defaults to an [AtomicNonce], which is only suitable when a single process uses the API
+    /// key. See the [nonce][crate::nonce] module for sources that can be shared between processes
+    pub fn set_nonce_source<S: NonceSource + 'static>(&mut self, source: S) {
+        self.nonce = NonceGenerator::new(source);
+    }
+
+    /// Replace how requests to private endpoints are signed, keeping the current API key
+    ///
+    /// Defaults to an [HmacSigner][crate::signer::HmacSigner] holding the secret given when the
+    /// client was constructed. When the secret is held by an external signer, the client can be
+    /// constructed with an empty secret:
+    ///
+    /// ```no_run
+    /// # use kraapi::client::KrakenClient;
+    /// # #[cfg(unix)]
+    /// # {
+    /// use kraapi::signer::UnixSocketSigner;
+    ///
+    /// let mut client = KrakenClient::new("<Your-API-Key>", "");
+    /// client.set_signer(UnixSocketSigner::new("/run/kraken-signer.sock"));
+    /// # }
+    /// ```
+    pub fn set_signer<S: Signer + 'static>(&mut self, signer: S) {
+        self.auth.set_signer(Arc::new(signer));
+    }
+
+    /// Set the two-factor authentication method configured on this client's API key. The `otp`
+    /// parameter is then added to every request sent to a private endpoint
+    pub fn set_two_factor(&mut self, two_factor: TwoFactor) {
+        self.two_factor = Some(two_factor);
+    }
+
+    /// Stop sending the `otp` parameter with private requests
+    pub fn clear_two_factor(&mut self) {
+        self.two_factor = None;
+    }
+
+    /// Register `middleware` to observe and modify every request made by this client and the
+    /// responses to them. Middleware registered first sees requests first and responses last. See
+    /// the [middleware module][crate::middleware]
+    pub fn add_middleware<M: Middleware + 'static>(&mut self, middleware: M) {
+        self.middleware.push(Box::new(middleware));
+    }
+
+    /// Record the requests made by this client and their latency and errors in `metrics`. See
+    /// the [metrics module][crate::metrics]
+    pub fn set_metrics(&mut self, metrics: KrakenMetrics) {
+        self.metrics = Some(metrics);
+    }
+
+    /// Connect to Kraken through `proxy`. See the [proxy module][crate::proxy]
+    ///
+    /// Connections already open to Kraken are closed
+    pub fn set_proxy(&mut self, proxy: Proxy) {
+        self.client = http_client(Some(proxy));
+    }
+
+    /// Stop connecting to Kraken through a proxy
+    pub fn clear_proxy(&mut self) {
+        self.client = http_client(None);
+    }
+
     /// Returns the current base url that this client will send requests to
     pub fn url(&self) -> &String {
         &self.url
@@ -78,6 +220,19 @@ impl KrakenClient {
         &self.version
     }
 
+    /// Returns whether this client is allowed to send requests to the withdrawal endpoints
+    pub fn withdrawals_enabled(&self) -> bool {
+        self.withdrawals
+    }
+
+    // The error is rebuilt for each request as io::Error can't be cloned
+    fn http(&self) -> KrakenResult<&hyper::Client<Connector, hyper::Body>> {
+        self.client.as_deref().map_err(|err| {
+            let err = io::Error::new(err.kind(), err.to_string());
+            error::KrakenErrors(vec![error::KError::TlsError(err)])
+        })
+    }
+
     fn auth(&self) -> &KrakenAuth {
         &self.auth
     }
@@ -86,138 +241,747 @@ impl KrakenClient {
     ///
     /// ## Note
     ///
-    /// The types of the input and the output must match otherwise the parsing will fail
+    /// The output type is carried by the input so `T` is checked at compile time. For instance: if
+    /// `input` is constructed from a KITicker instance, then `T` can only be KOTicker
     ///
-    /// For instance: if `input` is constructed from a KITicker instance, then `T` must be KOTicker
-    pub async fn request<'a, T>(&self, input: &KrakenInput) -> KrakenResult<T>
+    /// Private requests are assigned their nonce and signed here, when they are dispatched, so the
+    /// same [KrakenInput] can be sent any number of times and inputs can be built long before
+    /// they are sent. Nonces are issued in strictly increasing order across every request made by
+    /// this client, and a private request is only issued its nonce once Kraken has answered the
+    /// previous one so that concurrent requests can't reach Kraken out of order. Private requests
+    /// made through one client are therefore sent one at a time; use a
+    /// [KrakenClientPool][crate::pool::KrakenClientPool] with several keys to send them in parallel
+    pub async fn request<T>(&self, input: &KrakenInput<T>) -> KrakenResult<T>
     where
         T: Output + DeserializeOwned,
     {
-        match input.info().method() {
-            MethodType::Public => {
-                let endpoint = format!(
-                    "/{}/{}/{}",
-                    self.version(),
-                    input.info().method().to_string(),
-                    input.info().endpoint()
-                );
-                let formatted_params = api::format_params(&input.params());
-                let full_url = match formatted_params {
-                    Some(params) => format!("{}{}?{}", self.url(), endpoint, &params),
-                    None => format!("{}{}", self.url(), endpoint),
-                };
+        self.request_with_warnings(input)
+            .await
+            .map(|(result, _)| result)
+    }
 
-                let mut request = Request::builder()
-                    .method("GET")
-                    .uri(full_url)
-                    .body(Body::empty())
-                    .expect("Failed to form a correct http request");
+    /// Make a request in the same way as [request()][KrakenClient::request], also returning the
+    /// warnings Kraken sent alongside the result, such as the use of a deprecated parameter
+    pub async fn request_with_warnings<T>(
+        &self,
+        input: &KrakenInput<T>,
+    ) -> KrakenResult<(T, Vec<KWarning>)>
+    where
+        T: Output + DeserializeOwned,
+    {
+        self.execute(input, 1, |response| parse_response(&response))
+            .await
+    }
 
-                request.headers_mut().insert(
-                    USER_AGENT,
-                    "krakenapi/0.1 (Kraken Rust Client)".parse().unwrap(),
-                );
-                request.headers_mut().insert(
-                    CONTENT_TYPE,
-                    "application/x-www-form-urlencoded".parse().unwrap(),
-                );
+    /// Send an input builder or a finished [KrakenInput] to its endpoint, returning the output
+    /// type of that endpoint without having to name it
+    ///
+    /// ```no_run
+    /// # use kraapi::client::KrakenClient;
+    /// # use kraapi::public::ticker::KITicker;
+    /// # use kraapi::api::asset::{KAsset, KAssetPair};
+    /// # async fn ticker(client: &KrakenClient) -> kraapi::api::KrakenResult<()> {
+    /// // Parsed into a KOTicker
+    /// let ticker = client
+    ///     .send(KITicker::build(KAssetPair(KAsset::XBT, KAsset::USD)))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send<E>(&self, endpoint: E) -> KrakenResult<E::Output>
+    where
+        E: Endpoint,
+        E::Output: DeserializeOwned,
+    {
+        self.request(&endpoint.into_input()).await
+    }
+
+    /// Make a request to the endpoint of any [KrakenInput], returning the result as untyped json
+    /// instead of the endpoint's output type. Useful for fields or endpoints this crate doesn't
+    /// know about yet. See [KICustom][crate::api::custom::KICustom] for building input for
+    /// endpoints without a builder
+    pub async fn request_value<O>(&self, input: &KrakenInput<O>) -> KrakenResult<Value> {
+        self.execute(input, 1, |response| parse_response::<Value>(&response))
+            .await
+            .map(|(result, _)| result)
+    }
+
+    /// Retrieve a processed export report by passing a [KrakenInput] constructed from a
+    /// [KIRetrieveExport][crate::private::retrieve_export::KIRetrieveExport] instance
+    ///
+    /// ## Note
+    ///
+    /// Kraken responds to the retrieve data export endpoint with a zip archive instead of json so
+    /// it can't be sent with [request()][KrakenClient::request]
+    pub async fn request_export(
+        &self,
+        input: &KrakenInput<KORetrieveExport>,
+    ) -> KrakenResult<KORetrieveExport> {
+        self.execute(input, 1, parse_export).await
+    }
+
+    // Send `input` and parse the response within the span of the request. `attempt` counts the
+    // times the request has been sent, including this one
+    pub(crate) async fn execute<O, R, F>(
+        &self,
+        input: &KrakenInput<O>,
+        attempt: u32,
+        parse: F,
+    ) -> KrakenResult<R>
+    where
+        F: FnOnce(KrakenResponse) -> KrakenResult<R>,
+    {
+        let span = RequestSpan::new(input.info().endpoint(), *input.info().method(), attempt);
+        let start = Instant::now();
+        let result = span
+            .instrument(async { self.dispatch(input).await.and_then(parse) })
+            .await;
+        span.finish(&result);
+        if let Some(metrics) = &self.metrics {
+            metrics.record(
+                input.info().endpoint(),
+                *input.info().method(),
+                start.elapsed(),
+                &result,
+            );
+        }
+        result
+    }
+
+    pub(crate) async fn dispatch<O>(&self, input: &KrakenInput<O>) -> KrakenResult<KrakenResponse> {
+        if input.info().is_withdrawal() && !self.withdrawals_enabled() {
+            return Err(error::KrakenErrors(vec![error::KError::WithdrawalsDisabled]));
+        }
+
+        let method = *input.info().method();
+        let endpoint = format!(
+            "/{}/{}/{}",
+            self.version(),
+            method,
+            input.info().endpoint()
+        );
+        let mut unsigned = UnsignedRequest {
+            endpoint: input.info().endpoint().clone(),
+            method,
+            params: input.params().cloned().unwrap_or_default(),
+            lists: input.info().list_params(),
+        };
+        for middleware in &self.middleware {
+            middleware.before_sign(&mut unsigned)?;
+        }
+        trace::log_debug!(params = %trace::RedactedParams(&unsigned.params), "sending request");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_static("krakenapi/0.1 (Kraken Rust Client)"),
+        );
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
 
-                let parsed: KResult<T> = serde_json::from_slice(
-                    &body::to_bytes(self.client.request(request).await?).await?,
-                )?;
+        // Held from issuing the nonce of a private request until Kraken answers it
+        let mut nonce_order = None;
+        let mut request = match method {
+            MethodType::Public => {
+                let full_url = match unsigned.params.is_empty() {
+                    true => format!("{}{}", self.url(), endpoint),
+                    false => format!(
+                        "{}{}?{}",
+                        self.url(),
+                        endpoint,
+                        api::format_params(&Some(&unsigned.params), unsigned.lists)
+                            .unwrap_or_default()
+                    ),
+                };
 
-                let api_errors = parsed.error;
-                match api_errors.len() {
-                    0 => Ok(parsed.result.unwrap()),
-                    _ => Err(error::generate_errors(api_errors)),
+                SignedRequest {
+                    endpoint: unsigned.endpoint,
+                    method,
+                    http_method: Method::GET,
+                    uri: full_url,
+                    headers,
+                    body: String::new(),
                 }
             }
 
             MethodType::Private => {
-                let endpoint = format!(
-                    "/{}/{}/{}",
-                    self.version(),
-                    input.info().method().to_string(),
-                    input.info().endpoint()
-                );
-                let params = input.params();
-                let formatted_params = api::format_params(&params).unwrap();
-                // FIXME: Clean up the details behind get_params(), format_params() and KrakenInput
-                // It seems to work but the references are fragile
-                let signature = self.auth().sign(
-                    &endpoint,
-                    &params
-                        .expect("Add nonce when building private methods")
-                        .get("nonce")
-                        .expect("Add nonce when building private methods"),
-                    &formatted_params,
-                );
-                let full_url = format!("{}{}", self.url(), endpoint);
-
-                let mut request = Request::builder()
-                    .method("POST")
-                    .uri(full_url)
-                    .body(Body::from(formatted_params))
-                    .expect("Failed to form a correct http request");
+                self.auth().validate()?;
+                let mut params = unsigned.params;
+                let (order, nonce) = self
+                    .nonce
+                    .next()
+                    .await
+                    .map_err(|err| error::KrakenErrors(vec![error::KError::NonceError(err)]))?;
+                nonce_order = Some(order);
+                let nonce = nonce.to_string();
+                params.insert(String::from("nonce"), nonce.clone());
+                if let Some(two_factor) = &self.two_factor {
+                    params.insert(String::from("otp"), two_factor.code());
+                }
+                let formatted_params =
+                    api::format_params(&Some(&params), unsigned.lists).unwrap_or_default();
+                let signature = self.auth().sign(&endpoint, &nonce, &formatted_params).await?;
 
-                request.headers_mut().insert(
-                    USER_AGENT,
-                    "krakenapi/0.1 (Kraken Rust Client)".parse().unwrap(),
-                );
-                request.headers_mut().insert(
-                    CONTENT_TYPE,
-                    "application/x-www-form-urlencoded".parse().unwrap(),
+                let key = HeaderValue::from_str(self.auth().key())
+                    .map_err(|_| error::KrakenErrors(vec![error::KError::MalformedKey]))?;
+                headers.insert("API-Key", key);
+                headers.insert(
+                    "API-Sign",
+                    HeaderValue::from_str(&signature).map_err(http::Error::from)?,
                 );
-                request
-                    .headers_mut()
-                    .insert("API-Key", self.auth().key().parse().unwrap());
-                request
-                    .headers_mut()
-                    .insert("API-Sign", signature.parse().unwrap());
-
-                let parsed: KResult<T> = serde_json::from_slice(
-                    &body::to_bytes(self.client.request(request).await?).await?,
-                )?;
-
-                let api_errors = parsed.error;
-                match api_errors.len() {
-                    0 => Ok(parsed.result.unwrap()),
-                    _ => Err(error::generate_errors(api_errors)),
+
+                SignedRequest {
+                    endpoint: unsigned.endpoint,
+                    method,
+                    http_method: Method::POST,
+                    uri: format!("{}{}", self.url(), endpoint),
+                    headers,
+                    body: formatted_params,
                 }
             }
+        };
+        for middleware in &self.middleware {
+            middleware.after_sign(&mut request)?;
+        }
+
+        let mut http_request = Request::builder()
+            .method(request.http_method)
+            .uri(request.uri)
+            .body(Body::from(request.body))?;
+        *http_request.headers_mut() = request.headers;
+
+        let (parts, body) = self.http()?.request(http_request).await?.into_parts();
+        drop(nonce_order);
+        trace::record_status(parts.status.as_u16());
+        let mut response = RawResponse {
+            endpoint: request.endpoint,
+            method,
+            status: parts.status,
+            headers: parts.headers,
+            body: body::to_bytes(body).await?,
+        };
+        for middleware in self.middleware.iter().rev() {
+            middleware.on_response(&mut response)?;
+        }
+
+        Ok(KrakenResponse::from(response))
+    }
+}
+
+fn http_client(proxy: Option<Proxy>) -> io::Result<HttpClient> {
+    Ok(Box::new(
+        Client::builder()
+            .pool_idle_timeout(None)
+            .http1_title_case_headers(true)
+            .build::<_, hyper::Body>(Connector::new(proxy)?),
+    ))
+}
+
+// Response from Kraken along with what is needed to describe it when it can't be used
+pub(crate) struct KrakenResponse {
+    endpoint: String,
+    method: String,
+    status: StatusCode,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+}
+
+impl From<RawResponse> for KrakenResponse {
+    fn from(response: RawResponse) -> Self {
+        let headers = KErrorContext::HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = response.headers.get(*name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        KrakenResponse {
+            endpoint: response.endpoint,
+            method: response.method.to_string(),
+            status: response.status,
+            headers,
+            body: response.body,
+        }
+    }
+}
+
+impl KrakenResponse {
+    fn context(&self) -> Box<KErrorContext> {
+        Box::new(KErrorContext {
+            endpoint: self.endpoint.clone(),
+            method: self.method.clone(),
+            status: self.status.as_u16(),
+            headers: self.headers.clone(),
+            body: KErrorContext::truncate_body(&self.body),
+        })
+    }
+
+    // Syntax errors mean the body isn't json at all, while data errors mean it is json that
+    // doesn't match the expected structure
+    fn parse_error(&self, err: serde_json::Error) -> error::KrakenErrors<error::KError> {
+        let error = match err.classify() {
+            Category::Data => error::KError::ResponseParseError(err, self.context()),
+            _ => error::KError::NonJsonResponse(self.context()),
+        };
+        error::KrakenErrors(vec![error])
+    }
+
+    fn parse<T: DeserializeOwned>(&self) -> KrakenResult<KResult<T>> {
+        serde_json::from_slice(&self.body).map_err(|err| self.parse_error(err))
+    }
+
+    // Kraken reports its errors with a 200 status, so anything other than 2xx comes from
+    // somewhere else unless the body still holds errors in Kraken's format
+    fn check_status(&self) -> KrakenResult<()> {
+        if self.status.is_success() {
+            return Ok(());
+        }
+
+        let api_errors = serde_json::from_slice::<KResult<IgnoredAny>>(&self.body)
+            .map(|parsed| error::split_warnings(parsed.error).0)
+            .unwrap_or_default();
+        match api_errors.len() {
+            0 => Err(error::KrakenErrors(vec![error::KError::HttpStatus(
+                self.context(),
+            )])),
+            _ => Err(error::generate_errors(api_errors)),
         }
     }
 }
 
+pub(crate) fn parse_response<T>(response: &KrakenResponse) -> KrakenResult<(T, Vec<KWarning>)>
+where
+    T: Output + DeserializeOwned,
+{
+    response.check_status()?;
+    let parsed: KResult<T> = response.parse()?;
+
+    let (api_errors, warnings) = error::split_warnings(parsed.error);
+    match api_errors.len() {
+        0 => parsed
+            .result
+            .map(|result| (result, warnings))
+            .ok_or_else(|| error::KrakenErrors(vec![error::KError::MissingResult])),
+        _ => Err(error::generate_errors(api_errors)),
+    }
+}
+
+pub(crate) fn parse_export(response: KrakenResponse) -> KrakenResult<KORetrieveExport> {
+    response.check_status()?;
+    // Zip archives begin with the local file header signature. Anything else is Kraken's
+    // usual json response carrying the reason the report couldn't be retrieved
+    if response.body.starts_with(b"PK\x03\x04") {
+        return Ok(KORetrieveExport {
+            data: response.body.to_vec(),
+        });
+    }
+
+    let parsed: KResult<IgnoredAny> = response.parse()?;
+    let (api_errors, _) = error::split_warnings(parsed.error);
+    match api_errors.len() {
+        0 => Err(error::KrakenErrors(vec![error::KError::ArchiveError(
+            ZipError::InvalidArchive("Kraken did not respond with a zip archive"),
+        )])),
+        _ => Err(error::generate_errors(api_errors)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[test]
-    fn create_client() {
-        let mut client = KrakenClient::new("key", "secret");
+    #[tokio::test]
+    async fn create_client() {
+        use crate::signer::HmacSigner;
+
+        // The signature proves which secret the client signs with
+        async fn signature(client: &KrakenClient) -> KrakenResult<String> {
+            client.auth.sign("/0/private/Balance", "1", "nonce=1").await
+        }
+        let mut client = KrakenClient::new("key", "c2VjcmV0");
 
         assert_eq!(client.url, "https://api.kraken.com");
         assert_eq!(client.version, "0");
+        assert_eq!(client.auth.key(), "key");
         assert_eq!(
-            (
-                client.auth.key().to_owned(),
-                client.auth.secret().to_owned()
-            ),
-            (String::from("key"), String::from("secret"))
+            signature(&client).await.unwrap(),
+            HmacSigner::new("c2VjcmV0")
+                .sign("/0/private/Balance", "1", "nonce=1")
+                .unwrap()
         );
 
         client.set_url("https://new.url.com");
         client.set_version("2");
-        client.set_auth("newkey", "newsecret");
+        client.set_auth("newkey", "bmV3c2VjcmV0");
 
         assert_eq!(client.url, "https://new.url.com");
         assert_eq!(client.version, "2");
+        assert_eq!(client.auth.key(), "newkey");
         assert_eq!(
+            signature(&client).await.unwrap(),
+            HmacSigner::new("bmV3c2VjcmV0")
+                .sign("/0/private/Balance", "1", "nonce=1")
+                .unwrap()
+        );
+        assert!(!client.withdrawals_enabled());
+        assert!(KrakenClient::new_with_withdrawals("key", "secret").withdrawals_enabled());
+    }
+
+    #[tokio::test]
+    async fn withdrawals_disabled() {
+        use crate::api::asset::KAsset;
+        use crate::api::Input;
+        use crate::private::withdraw::{KIWithdraw, KOWithdraw};
+
+        let client = KrakenClient::new("key", "secret");
+        let input = KIWithdraw::build(KAsset::XBT, String::from("btc_cold"), 0.5).finish();
+
+        match client.request::<KOWithdraw>(&input).await {
+            Err(error::KrakenErrors(errors)) => {
+                assert!(matches!(errors.as_slice(), [error::KError::WithdrawalsDisabled]))
+            }
+            Ok(_) => panic!("Withdrawal should have been refused"),
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_credentials() {
+        use crate::private::account_balance::{KIAccountBalance, KOAccountBalance};
+
+        assert!(matches!(
+            KrakenClient::try_new("", "secret").err(),
+            Some(error::KrakenErrors(ref errors)) if matches!(errors[..], [error::KError::MalformedKey])
+        ));
+        assert!(KrakenClient::try_new("key", "c2VjcmV0").is_ok());
+
+        // Private requests with bad credentials are refused before reaching Kraken
+        let client = KrakenClient::new("key", "");
+        let input = KIAccountBalance::build();
+        match client.request::<KOAccountBalance>(&input).await {
+            Err(error::KrakenErrors(errors)) => {
+                assert!(matches!(
+                    errors.as_slice(),
+                    [error::KError::MalformedSecret]
+                ))
+            }
+            Ok(_) => panic!("Request with a malformed secret should have been refused"),
+        }
+    }
+
+    #[tokio::test]
+    async fn response_context() {
+        use crate::mock;
+        use crate::public::asset_info::KIAssetInfo;
+        use crate::public::server_time::{KIServerTime, KOServerTime};
+        use crate::public::system_status::{KISystemStatus, KOSystemStatus};
+
+        let url = mock::serve(|request| {
+            if request.contains("/time") {
+                (502, "text/html", String::from("<html>Bad Gateway</html>"))
+            } else if request.contains("/systemstatus") {
+                (200, "text/html", String::from("<html>Maintenance</html>"))
+            } else {
+                (
+                    200,
+                    "application/json",
+                    String::from(r#"{"error":[],"result":{"unixtime":"now"}}"#),
+                )
+            }
+        })
+        .await;
+        let metrics = KrakenMetrics::new();
+        let mut client = KrakenClient::new("", "");
+        client.set_url(&url);
+        client.set_metrics(metrics.clone());
+
+        let errors = client
+            .request::<KOServerTime>(&KIServerTime::build())
+            .await
+            .unwrap_err()
+            .0;
+        match &errors[..] {
+            [error::KError::HttpStatus(context)] => {
+                assert_eq!(
+                    (context.endpoint.as_str(), context.method.as_str()),
+                    ("Time", "public")
+                );
+                assert_eq!(context.status, 502);
+                assert_eq!(context.body, "<html>Bad Gateway</html>");
+                assert!(context
+                    .headers
+                    .contains(&(String::from("content-type"), String::from("text/html"))));
+            }
+            errors => panic!("Expected an http status error, found {:?}", errors),
+        }
+
+        let errors = client
+            .request::<KOSystemStatus>(&KISystemStatus::build())
+            .await
+            .unwrap_err()
+            .0;
+        assert!(
+            matches!(&errors[..], [error::KError::NonJsonResponse(context)] if context.endpoint == "SystemStatus")
+        );
+
+        // Valid json that doesn't match the output structure
+        let errors = client.send(KIAssetInfo::build()).await.unwrap_err().0;
+        assert!(
+            matches!(&errors[..], [error::KError::ResponseParseError(_, context)] if context.status == 200)
+        );
+
+        assert_eq!(metrics.requests("Time"), 1);
+        assert_eq!(metrics.errors("HttpStatus"), 1);
+        assert_eq!(
+            metrics.latency("Assets").map(|latency| latency.count()),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn inferred_output() {
+        use crate::api::asset::{KAsset, KAssetPair};
+        use crate::api::Input;
+        use crate::mock;
+        use crate::public::ticker::KITicker;
+
+        let url = mock::serve(|_| {
+            (
+                200,
+                "application/json",
+                String::from(
+                    r#"{"error":[],"result":{"XXBTZUSD":{"a":["30300.1","1","1.000"],
+                    "b":["30300.0","1","1.000"],"c":["30303.2","0.001"],"v":["1.5","20.1"],
+                    "p":["30289.6","30350.4"],"t":[12,312],"l":["30240.2","30108.3"],
+                    "h":["30345.1","30640.0"],"o":"30502.8"}}}"#,
+                ),
+            )
+        })
+        .await;
+        let mut client = KrakenClient::new("", "");
+        client.set_url(&url);
+        let pair = KAssetPair(KAsset::XBT, KAsset::USD);
+
+        // Neither a builder nor a finished input needs the output type spelled out
+        let ticker = client.send(KITicker::build(pair)).await.unwrap();
+        assert_eq!(ticker.pair[&pair].o, "30502.8");
+        let input = KITicker::build(pair).finish();
+        let ticker = client.request(&input).await.unwrap();
+        assert_eq!(ticker.pair[&pair].t, vec![12, 312]);
+        let ticker = client.send(input).await.unwrap();
+        assert_eq!(ticker.pair.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn custom_endpoint() {
+        use crate::api::custom::KICustom;
+        use crate::mock;
+        use crate::public::server_time::KIServerTime;
+        use serde::Deserialize;
+
+        #[derive(Deserialize)]
+        struct KOLaunch {
+            launched: bool,
+        }
+
+        impl Output for KOLaunch {}
+
+        let url = mock::serve(|request| {
+            let body = if request.starts_with("get /0/public/launch?asset=dot ") {
+                r#"{"error":[],"result":{"launched":true}}"#
+            } else {
+                r#"{"error":[],"result":{"unixtime":1688669448,"rfc1123":"Thu, 06 Jul 23"}}"#
+            };
+            (200, "application/json", String::from(body))
+        })
+        .await;
+        let mut client = KrakenClient::new("", "");
+        client.set_url(&url);
+
+        let launch = client
+            .send(
+                KICustom::<KOLaunch>::build(MethodType::Public, "Launch")
+                    .with_param("asset", "DOT"),
+            )
+            .await
+            .unwrap();
+        assert!(launch.launched);
+
+        let time = client.request_value(&KIServerTime::build()).await.unwrap();
+        assert_eq!(time["unixtime"], 1688669448);
+    }
+
+    #[tokio::test]
+    async fn middleware_chain() {
+        use crate::middleware::Middleware;
+        use crate::mock;
+        use crate::private::account_balance::KIAccountBalance;
+        use std::sync::{Arc, Mutex};
+
+        struct Recorder {
+            name: &'static str,
+            log: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Middleware for Recorder {
+            fn before_sign(&self, request: &mut UnsignedRequest) -> KrakenResult<()> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} before", self.name));
+                if self.name == "outer" {
+                    request.set_param("userref", "7");
+                }
+                Ok(())
+            }
+
+            fn after_sign(&self, request: &mut SignedRequest) -> KrakenResult<()> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} after", self.name));
+                if self.name == "inner" {
+                    assert!(request.body().starts_with("userref=7&nonce="));
+                    assert!(request.headers().contains_key("API-Sign"));
+                    request
+                        .headers_mut()
+                        .insert("X-Audit", HeaderValue::from_static("1"));
+                }
+                Ok(())
+            }
+
+            fn on_response(&self, response: &mut RawResponse) -> KrakenResult<()> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} response", self.name));
+                if self.name == "outer" {
+                    assert_eq!(response.body(), br#"{"error":[],"result":{}}"#);
+                    response.set_body(r#"{"error":["EService:Unavailable"]}"#);
+                }
+                Ok(())
+            }
+        }
+
+        let url = mock::serve(|request| {
+            assert!(request.contains("x-audit: 1"));
             (
-                client.auth.key().to_owned(),
-                client.auth.secret().to_owned()
-            ),
-            (String::from("newkey"), String::from("newsecret"))
+                200,
+                "application/json",
+                String::from(r#"{"error":[],"result":{}}"#),
+            )
+        })
+        .await;
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut client = KrakenClient::new("key", "c2VjcmV0");
+        client.set_url(&url);
+        for name in ["outer", "inner"] {
+            client.add_middleware(Recorder {
+                name,
+                log: log.clone(),
+            });
+        }
+
+        let errors = client.send(KIAccountBalance::build()).await.unwrap_err().0;
+        assert!(matches!(
+            &errors[..],
+            [error::KError::ServiceUnavailable(_)]
+        ));
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer before",
+                "inner before",
+                "outer after",
+                "inner after",
+                "inner response",
+                "outer response"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn wallet_transfer() {
+        use crate::api::asset::KAsset;
+        use crate::mock;
+        use crate::private::wallet_transfer::KIWalletTransfer;
+
+        let url = mock::serve(|request| {
+            let body = request.rsplit("\r\n").next().unwrap_or_default();
+            assert!(body.starts_with("asset=xbt&from=spot+wallet&to=futures+wallet&amount=0.25&"));
+            (
+                200,
+                "application/json",
+                String::from(r#"{"error":[],"result":{"refid":"BOG5AE5-KSCNR4-VPNPEV"}}"#),
+            )
+        })
+        .await;
+        let mut client = KrakenClient::new("key", "c2VjcmV0");
+        client.set_url(&url);
+
+        let transfer = client
+            .send(KIWalletTransfer::build(KAsset::XBT, 0.25))
+            .await
+            .unwrap();
+        assert_eq!(transfer.refid, "BOG5AE5-KSCNR4-VPNPEV");
+
+        // Templates can't name another wallet
+        let template = r#"{"from":"Futures Wallet","to":"Spot Wallet","asset":"XBT","amount":"1"}"#;
+        assert!(serde_json::from_str::<KIWalletTransfer>(template).is_err());
+    }
+
+    #[tokio::test]
+    async fn nonce_order() {
+        use crate::mock;
+        use crate::private::account_balance::KIAccountBalance;
+        use crate::signer::{HmacSigner, Signer};
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        struct CountingNonce(AtomicU64);
+
+        impl NonceSource for CountingNonce {
+            fn next_nonce(&self) -> io::Result<u64> {
+                Ok(self.0.fetch_add(1, Ordering::SeqCst))
+            }
+        }
+
+        // Takes longer to sign requests with an even nonce, so that without ordering the request
+        // issued the next nonce would overtake them
+        struct SlowSigner(HmacSigner);
+
+        impl Signer for SlowSigner {
+            fn sign(&self, path: &str, nonce: &str, params: &str) -> KrakenResult<String> {
+                if nonce.parse::<u64>().unwrap() % 2 == 0 {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                self.0.sign(path, nonce, params)
+            }
+        }
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        let url = mock::serve(move |request| {
+            let nonce = request.split("nonce=").nth(1).unwrap_or_default();
+            log.lock().unwrap().push(nonce.parse::<u64>().unwrap());
+            (200, "application/json", String::from(r#"{"error":[],"result":{}}"#))
+        })
+        .await;
+        let mut client = KrakenClient::new("key", "c2VjcmV0");
+        client.set_url(&url);
+        client.set_nonce_source(CountingNonce(AtomicU64::new(1)));
+        client.set_signer(SlowSigner(HmacSigner::new("c2VjcmV0")));
+
+        let input = KIAccountBalance::build();
+        let results = tokio::join!(
+            client.request(&input),
+            client.request(&input),
+            client.request(&input),
+            client.request(&input),
         );
+        assert!(results.0.is_ok() && results.1.is_ok() && results.2.is_ok() && results.3.is_ok());
+        assert_eq!(*received.lock().unwrap(), [1, 2, 3, 4]);
     }
 }
@@ -0,0 +1,208 @@
+// Connector opening the connections of a KrakenClient, directly or through a proxy, and
+// negotiating TLS with the backend selected by the rustls and native-tls features
+
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use hyper::service::Service;
+use hyper::Uri;
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+use crate::proxy::Proxy;
+
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+compile_error!("Either the rustls or the native-tls feature must be enabled");
+
+#[cfg(feature = "rustls")]
+type TlsStream = tokio_rustls::client::TlsStream<TcpStream>;
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+type TlsStream = tokio_native_tls::TlsStream<TcpStream>;
+// Only there so that the compile_error above is the only error reported without a backend
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+type TlsStream = TcpStream;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct Connector {
+    http: HttpConnector,
+    proxy: Option<Arc<Proxy>>,
+    tls: TlsConnector,
+}
+
+impl Connector {
+    // Fails if the TLS backend can't be initialized, e.g. if native-tls fails to load the
+    // system's certificates
+    pub(crate) fn new(proxy: Option<Proxy>) -> io::Result<Self> {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        Ok(Connector {
+            http,
+            proxy: proxy.map(Arc::new),
+            tls: TlsConnector::new()?,
+        })
+    }
+
+    async fn connect(mut self, dst: Uri) -> Result<MaybeTls, BoxError> {
+        let https = dst.scheme() == Some(&http::uri::Scheme::HTTPS);
+        let host = dst
+            .host()
+            .ok_or("url of the request has no host")?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = dst.port_u16().unwrap_or(if https { 443 } else { 80 });
+
+        let tcp = match &self.proxy {
+            None => self.http.call(dst).await?,
+            Some(proxy) => {
+                let mut tcp = self.http.call(proxy.uri().parse()?).await?;
+                proxy.tunnel(&mut tcp, &host, port).await?;
+                tcp
+            }
+        };
+
+        match https {
+            true => Ok(MaybeTls::Tls(Box::new(self.tls.connect(&host, tcp).await?))),
+            false => Ok(MaybeTls::Plain(tcp)),
+        }
+    }
+}
+
+impl Service<Uri> for Connector {
+    type Response = MaybeTls;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<MaybeTls, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        Box::pin(self.clone().connect(dst))
+    }
+}
+
+#[derive(Clone)]
+struct TlsConnector {
+    #[cfg(feature = "rustls")]
+    inner: tokio_rustls::TlsConnector,
+    #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+    inner: tokio_native_tls::TlsConnector,
+}
+
+impl TlsConnector {
+    #[cfg(feature = "rustls")]
+    fn new() -> io::Result<Self> {
+        use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore};
+
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(TlsConnector {
+            inner: tokio_rustls::TlsConnector::from(Arc::new(config)),
+        })
+    }
+
+    #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+    fn new() -> io::Result<Self> {
+        let connector =
+            tokio_native_tls::native_tls::TlsConnector::new().map_err(io::Error::other)?;
+        Ok(TlsConnector {
+            inner: tokio_native_tls::TlsConnector::from(connector),
+        })
+    }
+
+    #[cfg(feature = "rustls")]
+    async fn connect(&self, host: &str, tcp: TcpStream) -> Result<TlsStream, BoxError> {
+        use tokio_rustls::rustls::ServerName;
+
+        let name = ServerName::try_from(host)?;
+        Ok(self.inner.connect(name, tcp).await?)
+    }
+
+    #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+    async fn connect(&self, host: &str, tcp: TcpStream) -> Result<TlsStream, BoxError> {
+        Ok(self.inner.connect(host, tcp).await?)
+    }
+
+    #[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+    fn new() -> io::Result<Self> {
+        Err(io::Error::other("no TLS backend enabled"))
+    }
+
+    #[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+    async fn connect(&self, _: &str, _: TcpStream) -> Result<TlsStream, BoxError> {
+        Err("no TLS backend enabled".into())
+    }
+}
+
+// Connection to Kraken, encrypted unless the client's url is plain http
+pub(crate) enum MaybeTls {
+    Plain(TcpStream),
+    Tls(Box<TlsStream>),
+}
+
+impl Connection for MaybeTls {
+    fn connected(&self) -> Connected {
+        match self {
+            MaybeTls::Plain(tcp) => tcp.connected(),
+            MaybeTls::Tls(_) => Connected::new(),
+        }
+    }
+}
+
+impl AsyncRead for MaybeTls {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTls::Plain(tcp) => Pin::new(tcp).poll_read(cx, buf),
+            MaybeTls::Tls(tls) => Pin::new(tls.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for MaybeTls {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            MaybeTls::Plain(tcp) => Pin::new(tcp).poll_write(cx, buf),
+            MaybeTls::Tls(tls) => Pin::new(tls.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTls::Plain(tcp) => Pin::new(tcp).poll_flush(cx),
+            MaybeTls::Tls(tls) => Pin::new(tls.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTls::Plain(tcp) => Pin::new(tcp).poll_shutdown(cx),
+            MaybeTls::Tls(tls) => Pin::new(tls.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! Module containing the ways API credentials can be loaded and how they are held in memory
+//!
+//! Credentials are stored in [Secret]s, which are zeroed when dropped and never print their
+//! value through [Debug][std::fmt::Debug]. Rather than passing the key and secret to
+//! [KrakenClient::new()][super::client::KrakenClient::new] as string literals, they can be loaded
+//! by any [CredentialProvider] and given to
+//! [KrakenClient::from_provider()][super::client::KrakenClient::from_provider]:
+//!
+//! - [EnvCredentials] reads them from environment variables
+//! - [FileCredentials] reads them from a file which only its owner can access
+//! - [EncryptedKeyfile] decrypts them from a file protected with a password
+//!
+//! ```no_run
+//! # use kraapi::client::KrakenClient;
+//! # use kraapi::credentials::{EncryptedKeyfile, EnvCredentials};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Reads KRAKEN_API_KEY and KRAKEN_API_SECRET
+//! let client = KrakenClient::from_provider(&EnvCredentials::new())?;
+//!
+//! let keyfile = EncryptedKeyfile::new("kraken.key", "correct horse battery staple");
+//! let client = KrakenClient::from_provider(&keyfile)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use aes_gcm::aead::{Aead, NewAead};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use hmac::Hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{KError, KrakenErrors};
+
+/// String holding sensitive data such as an API secret
+///
+/// The contents are zeroed when the Secret is dropped and are redacted when debug printed. Use
+/// [expose()][Secret::expose] to read the value
+#[derive(Clone, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Wrap `value` in a Secret
+    pub fn new(value: String) -> Self {
+        Secret(value)
+    }
+
+    /// Returns the secret value
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns whether the secret is an empty string
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret([REDACTED])")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// API key and secret used to sign requests to private endpoints
+#[derive(Clone, Debug)]
+pub struct Credentials {
+    key: Secret,
+    secret: Secret,
+}
+
+impl Credentials {
+    /// Construct a new set of Credentials from the API key and the base64 encoded API secret
+    pub fn new<K: Into<Secret>, S: Into<Secret>>(key: K, secret: S) -> Self {
+        Credentials {
+            key: key.into(),
+            secret: secret.into(),
+        }
+    }
+
+    /// Returns the API key
+    pub fn key(&self) -> &Secret {
+        &self.key
+    }
+
+    /// Returns the base64 encoded API secret
+    pub fn secret(&self) -> &Secret {
+        &self.secret
+    }
+
+    // Credentials files hold the key on the first line and the secret on the second
+    fn parse(contents: &str) -> Result<Self, KrakenErrors<KError>> {
+        let mut lines = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+        match (lines.next(), lines.next(), lines.next()) {
+            (Some(key), Some(secret), None) => Ok(Credentials::new(key, secret)),
+            _ => Err(KrakenErrors(vec![KError::CredentialsIoError(
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "expected the API key and the API secret on separate lines",
+                ),
+            )])),
+        }
+    }
+
+    fn to_contents(&self) -> Zeroizing<String> {
+        Zeroizing::new(format!("{}\n{}\n", self.key.expose(), self.secret.expose()))
+    }
+}
+
+fn io_error(err: io::Error) -> KrakenErrors<KError> {
+    KrakenErrors(vec![KError::CredentialsIoError(err)])
+}
+
+/// Source of the API credentials used by a [KrakenClient][super::client::KrakenClient]
+pub trait CredentialProvider {
+    /// Load the credentials
+    fn credentials(&self) -> Result<Credentials, KrakenErrors<KError>>;
+}
+
+impl CredentialProvider for Credentials {
+    fn credentials(&self) -> Result<Credentials, KrakenErrors<KError>> {
+        Ok(self.clone())
+    }
+}
+
+/// Loads the credentials from environment variables
+#[derive(Debug)]
+pub struct EnvCredentials {
+    key_var: String,
+    secret_var: String,
+}
+
+impl EnvCredentials {
+    /// Environment variable holding the API key unless set otherwise
+    pub const KEY_VAR: &'static str = "KRAKEN_API_KEY";
+
+    /// Environment variable holding the API secret unless set otherwise
+    pub const SECRET_VAR: &'static str = "KRAKEN_API_SECRET";
+
+    /// Read the credentials from `KRAKEN_API_KEY` and `KRAKEN_API_SECRET`
+    pub fn new() -> Self {
+        EnvCredentials::with_vars(EnvCredentials::KEY_VAR, EnvCredentials::SECRET_VAR)
+    }
+
+    /// Read the credentials from the environment variables `key_var` and `secret_var`
+    pub fn with_vars(key_var: &str, secret_var: &str) -> Self {
+        EnvCredentials {
+            key_var: key_var.to_string(),
+            secret_var: secret_var.to_string(),
+        }
+    }
+
+    fn var(name: &str) -> Result<Secret, KrakenErrors<KError>> {
+        env::var(name)
+            .map(Secret::new)
+            .map_err(|_| KrakenErrors(vec![KError::MissingCredentials(name.to_string())]))
+    }
+}
+
+impl Default for EnvCredentials {
+    fn default() -> Self {
+        EnvCredentials::new()
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn credentials(&self) -> Result<Credentials, KrakenErrors<KError>> {
+        Ok(Credentials {
+            key: EnvCredentials::var(&self.key_var)?,
+            secret: EnvCredentials::var(&self.secret_var)?,
+        })
+    }
+}
+
+/// Loads the credentials from a plain text file holding the API key on the first line and the
+/// API secret on the second
+///
+/// On Unix the file is refused with
+/// [InsecureCredentialsFile][crate::error::KError::InsecureCredentialsFile] if its group or other
+/// users have any permissions on it (anything looser than `chmod 600`)
+#[derive(Debug)]
+pub struct FileCredentials {
+    path: PathBuf,
+}
+
+impl FileCredentials {
+    /// Read the credentials from the file at `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileCredentials {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn credentials(&self) -> Result<Credentials, KrakenErrors<KError>> {
+        check_permissions(&self.path)?;
+        let contents = Zeroizing::new(fs::read_to_string(&self.path).map_err(io_error)?);
+        Credentials::parse(&contents)
+    }
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), KrakenErrors<KError>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    match fs::metadata(path).map_err(io_error)?.permissions().mode() & 0o077 {
+        0 => Ok(()),
+        _ => Err(KrakenErrors(vec![KError::InsecureCredentialsFile(
+            path.to_path_buf(),
+        )])),
+    }
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), KrakenErrors<KError>> {
+    Ok(())
+}
+
+#[derive(Deserialize, Serialize)]
+struct KeyfileContents {
+    version: u32,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Loads the credentials from a keyfile encrypted with a password
+///
+/// The keyfile is json holding the credentials encrypted with AES-256-GCM, using a key derived
+/// from the password with PBKDF2-HMAC-SHA256. Create one with [write()][EncryptedKeyfile::write]
+#[derive(Debug)]
+pub struct EncryptedKeyfile {
+    path: PathBuf,
+    password: Secret,
+    iterations: u32,
+}
+
+impl EncryptedKeyfile {
+    /// Default number of PBKDF2 iterations used when writing new keyfiles
+    pub const ITERATIONS: u32 = 600_000;
+
+    /// Largest number of PBKDF2 iterations accepted. Keyfiles recording more are rejected rather
+    /// than spending minutes deriving the key
+    pub const MAX_ITERATIONS: u32 = 10_000_000;
+
+    const VERSION: u32 = 1;
+
+    /// Decrypt the keyfile at `path` with `password`
+    pub fn new<P: AsRef<Path>, S: Into<Secret>>(path: P, password: S) -> Self {
+        EncryptedKeyfile {
+            path: path.as_ref().to_path_buf(),
+            password: password.into(),
+            iterations: EncryptedKeyfile::ITERATIONS,
+        }
+    }
+
+    /// Set the number of PBKDF2 iterations used by [write()][EncryptedKeyfile::write], up to
+    /// [MAX_ITERATIONS][EncryptedKeyfile::MAX_ITERATIONS]. Keyfiles record their iteration count,
+    /// so this has no effect on decryption
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.clamp(1, EncryptedKeyfile::MAX_ITERATIONS);
+        self
+    }
+
+    /// Encrypt `credentials` with the password and write them to the keyfile, replacing it if it
+    /// already exists. On Unix the keyfile is made readable only by its owner before the
+    /// credentials are written, including when it already existed with wider permissions
+    pub fn write(&self, credentials: &Credentials) -> Result<(), KrakenErrors<KError>> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        getrandom::getrandom(&mut salt)
+            .and_then(|_| getrandom::getrandom(&mut nonce))
+            .map_err(|err| io_error(io::Error::other(err.to_string())))?;
+
+        let cipher = self.cipher(&salt, self.iterations);
+        let ciphertext = cipher
+            .encrypt(&Nonce::from(nonce), credentials.to_contents().as_bytes())
+            .map_err(|_| KrakenErrors(vec![KError::KeyfileError]))?;
+
+        let contents = serde_json::to_vec(&KeyfileContents {
+            version: EncryptedKeyfile::VERSION,
+            iterations: self.iterations,
+            salt: base64::encode(salt),
+            nonce: base64::encode(nonce),
+            ciphertext: base64::encode(ciphertext),
+        })?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&self.path).map_err(io_error)?;
+        // The mode only applies when the file is created, so an existing keyfile keeps its
+        // permissions unless they are set here
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))
+                .map_err(io_error)?;
+        }
+        file.write_all(&contents).map_err(io_error)
+    }
+
+    fn cipher(&self, salt: &[u8], iterations: u32) -> Aes256Gcm {
+        let mut key = Key::default();
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(
+            self.password.expose().as_bytes(),
+            salt,
+            iterations,
+            &mut key,
+        );
+        let cipher = Aes256Gcm::new(&key);
+        key[..].zeroize();
+        cipher
+    }
+}
+
+impl CredentialProvider for EncryptedKeyfile {
+    fn credentials(&self) -> Result<Credentials, KrakenErrors<KError>> {
+        let keyfile: KeyfileContents =
+            serde_json::from_slice(&fs::read(&self.path).map_err(io_error)?)?;
+        let keyfile_error = || KrakenErrors(vec![KError::KeyfileError]);
+
+        if keyfile.version != EncryptedKeyfile::VERSION
+            || keyfile.iterations > EncryptedKeyfile::MAX_ITERATIONS
+        {
+            return Err(keyfile_error());
+        }
+        let salt = base64::decode(&keyfile.salt).map_err(|_| keyfile_error())?;
+        let nonce: [u8; 12] = base64::decode(&keyfile.nonce)
+            .ok()
+            .and_then(|nonce| nonce.try_into().ok())
+            .ok_or_else(keyfile_error)?;
+        let ciphertext = base64::decode(&keyfile.ciphertext).map_err(|_| keyfile_error())?;
+
+        let contents = self
+            .cipher(&salt, keyfile.iterations)
+            .decrypt(&Nonce::from(nonce), ciphertext.as_slice())
+            .map_err(|_| keyfile_error())?;
+        let contents = Zeroizing::new(String::from_utf8(contents).map_err(|_| keyfile_error())?);
+        Credentials::parse(&contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("kraapi-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let credentials = Credentials::new("key", "c2VjcmV0");
+        let debug = format!("{:?}", credentials);
+
+        assert!(!debug.contains("key\""));
+        assert!(!debug.contains("c2VjcmV0"));
+        assert_eq!(credentials.secret().expose(), "c2VjcmV0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path("credentials");
+        fs::write(&path, "key\nc2VjcmV0\n").unwrap();
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(matches!(
+            FileCredentials::new(&path).credentials().unwrap_err().0[..],
+            [KError::InsecureCredentialsFile(_)]
+        ));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        let credentials = FileCredentials::new(&path).credentials().unwrap();
+        assert_eq!(credentials.key().expose(), "key");
+        assert_eq!(credentials.secret().expose(), "c2VjcmV0");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn encrypted_keyfile() {
+        let path = temp_path("keyfile");
+        EncryptedKeyfile::new(&path, "password")
+            .with_iterations(1_000)
+            .write(&Credentials::new("key", "c2VjcmV0"))
+            .unwrap();
+
+        let credentials = EncryptedKeyfile::new(&path, "password")
+            .credentials()
+            .unwrap();
+        assert_eq!(credentials.key().expose(), "key");
+        assert_eq!(credentials.secret().expose(), "c2VjcmV0");
+        assert!(matches!(
+            EncryptedKeyfile::new(&path, "wrong")
+                .credentials()
+                .unwrap_err()
+                .0[..],
+            [KError::KeyfileError]
+        ));
+
+        // A keyfile claiming an absurd iteration count is rejected without deriving the key
+        let mut keyfile: serde_json::Value =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        keyfile["iterations"] = u32::MAX.into();
+        fs::write(&path, keyfile.to_string()).unwrap();
+        assert!(matches!(
+            EncryptedKeyfile::new(&path, "password")
+                .credentials()
+                .unwrap_err()
+                .0[..],
+            [KError::KeyfileError]
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keyfile_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Overwriting an existing keyfile tightens its permissions
+        let path = temp_path("keyfile-permissions");
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        EncryptedKeyfile::new(&path, "password")
+            .with_iterations(1_000)
+            .write(&Credentials::new("key", "c2VjcmV0"))
+            .unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,15 +2,63 @@
 
 use std::error::Error;
 use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use csv::Error as CsvError;
+use http::Error as HttpRequestError;
 use hyper::Error as HyperError;
 use serde_json::Error as SerdeError;
+use zip::result::ZipError;
 
 /// Newtype wrapper around a vector of error values
 #[derive(Debug)]
 pub struct KrakenErrors<KError>(pub Vec<KError>);
 
-impl Error for KrakenErrors<KError> {}
+impl Error for KrakenErrors<KError> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.first().and_then(|error| error.source())
+    }
+}
+
+impl KrakenErrors<KError> {
+    /// Returns whether sending the same request again may succeed, which is the case when every
+    /// error is retryable. See [KError::is_retryable]
+    pub fn is_retryable(&self) -> bool {
+        !self.0.is_empty() && self.0.iter().all(KError::is_retryable)
+    }
+
+    /// Returns whether any of the errors is caused by a rate limit
+    pub fn is_rate_limit(&self) -> bool {
+        self.0.iter().any(KError::is_rate_limit)
+    }
+
+    /// Returns whether any of the errors is caused by invalid or missing credentials
+    pub fn is_auth_failure(&self) -> bool {
+        self.0.iter().any(KError::is_auth_failure)
+    }
+
+    /// Returns whether any of the errors is caused by the funds or margin available
+    pub fn is_funds_related(&self) -> bool {
+        self.0.iter().any(KError::is_funds_related)
+    }
+
+    /// Returns whether retrying the request can't succeed without changing it or the client
+    pub fn is_fatal(&self) -> bool {
+        !self.is_retryable()
+    }
+
+    /// Returns how long to wait before sending the request again, which is the longest wait
+    /// suggested by any of the errors. `None` if the request shouldn't be retried
+    pub fn suggested_backoff(&self) -> Option<Duration> {
+        self.0
+            .iter()
+            .map(KError::suggested_backoff)
+            .try_fold(Duration::ZERO, |longest, backoff| Some(longest.max(backoff?)))
+            .filter(|_| !self.0.is_empty())
+    }
+}
 
 impl fmt::Display for KrakenErrors<KError> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -32,25 +80,107 @@ pub enum KError {
     /// Wrapper around [hyper::Error] when an internal http error has occurred
     HttpError(HyperError),
 
-    /// Wrapper around [serde_json::Error] for when serde fails to parse the json into the output
-    /// structure
+    /// Wrapper around [serde_json::Error] for when serde fails to parse json, such as an
+    /// encrypted keyfile
     ParseError(SerdeError),
 
-    /// Failed to parse into KAsset/KAssetPair
-    AssetParseError,
+    /// Kraken's response is json but doesn't match the output structure, which usually means
+    /// the input and output types don't match or Kraken changed the endpoint
+    ResponseParseError(SerdeError, Box<KErrorContext>),
+
+    /// Kraken, or a proxy or load balancer in front of it, responded with an http status other
+    /// than 2xx and without any errors in Kraken's usual format
+    HttpStatus(Box<KErrorContext>),
+
+    /// The response body is not json, such as an html error page
+    NonJsonResponse(Box<KErrorContext>),
+
+    /// Wrapper around [zip::result::ZipError] for when an export report could not be unzipped
+    ArchiveError(ZipError),
+
+    /// Wrapper around [csv::Error] for when an export report could not be parsed into the
+    /// output structure
+    CsvError(CsvError),
+
+    /// The client's [NonceSource][super::nonce::NonceSource] failed to issue a nonce, so the
+    /// request was never sent to Kraken
+    NonceError(io::Error),
+
+    /// The API key is empty or contains characters that can't be sent in an http header
+    MalformedKey,
+
+    /// The API secret is empty or is not valid base64
+    MalformedSecret,
+
+    /// A [CredentialProvider][super::credentials::CredentialProvider] couldn't find the named
+    /// credential, such as an unset environment variable
+    MissingCredentials(String),
+
+    /// The credentials file at the given path can be accessed by users other than its owner, so
+    /// it was not read
+    InsecureCredentialsFile(PathBuf),
+
+    /// Failed to read or write a credentials file, or its contents are malformed
+    CredentialsIoError(io::Error),
+
+    /// An [EncryptedKeyfile][super::credentials::EncryptedKeyfile] could not be decrypted, either
+    /// because the password is wrong or because the keyfile is corrupted, or could not be
+    /// encrypted
+    KeyfileError,
+
+    /// The client's [Signer][super::signer::Signer] failed to sign the request, so it was never
+    /// sent to Kraken
+    SignerError(io::Error),
+
+    /// Wrapper around [http::Error] for when the http request to Kraken could not be formed
+    RequestError(HttpRequestError),
+
+    /// Kraken responded without either a result or any errors
+    MissingResult,
+
+    /// A [KrakenClientPool][super::pool::KrakenClientPool] has no usable key for the request,
+    /// either because no key has the needed role or because every such key is invalid or locked
+    /// out
+    NoAvailableKey,
+
+    /// The TOTP secret given to [TwoFactor][super::otp::TwoFactor] is not valid base32
+    InvalidOtpSecret,
+
+    /// Failed to parse into KAsset/KAssetPair. Holds the string that couldn't be parsed
+    AssetParseError(String),
+
+    /// Failed to parse into [KTimestamp][super::api::timestamp::KTimestamp]
+    TimestampParseError,
+
+    /// A withdrawal endpoint was requested on a [KrakenClient][super::client::KrakenClient] that
+    /// was not constructed with
+    /// [new_with_withdrawals()][super::client::KrakenClient::new_with_withdrawals]. The request
+    /// was never sent to Kraken
+    WithdrawalsDisabled,
+
+    /// Failed to parse into a [Proxy][super::proxy::Proxy]. Holds the reason the proxy url was
+    /// rejected
+    InvalidProxy(String),
+
+    /// The TLS backend could not be initialized, e.g. because native-tls failed to load the
+    /// system's certificates, so no request can be sent to Kraken
+    TlsError(io::Error),
+
+    // Errors returned by Kraken. Each holds the raw error string Kraken returned, such as
+    // "EAPI:Invalid key", including any extra information appended to it
 
     /// Invalid currency pair
     /// You can pull the complete list of our asset pairs from the AssetPairs public call
     /// and look for the pair name as the entry of the Json headers or by the parameter
     /// "altname": `https://api.kraken.com/0/public/AssetPairs`
-    UnknownAssetPair,
+    UnknownAssetPair(String),
 
     /// This error is returned when a method is called without the required parameters.
     /// For example, calling the QueryOrders method without specifying a valid transaction
     /// id (txid) parameter would cause the invalid arguments error to be returned.
     /// Calling a method with unnecessary parameters would still not return the
     /// invalid arguments error because the unnecessary parameters would simply be ignored.
-    InvalidArguments,
+    InvalidArguments(String),
 
     /// Permission denied errors are returned when the API client is attempting a task
     /// for which the API key does not have permission. For example, if an API client
@@ -58,34 +188,34 @@ pub enum KError {
     /// allow trading access but not account management access, then the permission denied
     /// error would be returned. You can review your API keys and their settings
     /// (such as their permissions) via the Settings -> API tab of account management.
-    PermissionDenied,
+    PermissionDenied(String),
 
     /// This error is returned when the API key used for the call is either expired or disabled,
     /// please review the API key in your Settings -> API tab of account management or
     /// generate a new one and update your application.
-    InvalidKey,
+    InvalidKey(String),
 
     /// The Invalid Key error occurs if either your API key or API secret are written
     /// incorrectly in your program or because the POST data used in the authentication
     /// and the POST data sent to the API do not match
-    InvalidSignature,
+    InvalidSignature(String),
 
     /// This error is returned when an invalid nonce is sent.
     /// Check your [nonce
     /// window](https://support.kraken.com/hc/en-us/articles/360001148023-What-is-a-Nonce-Window-)
-    InvalidNonce,
+    InvalidNonce(String),
 
     /// This error occurs when the [API call
     /// limits](https://support.kraken.com/hc/en-us/articles/206548367-What-is-the-API-call-rate-limit-)
     /// are exceeded
-    APIRateLimit,
+    APIRateLimit(String),
 
     /// While adding/canceling orders does not count against our standard API counter limits,
     /// these operations do have their own add/cancel order counter. This counter works in a
     /// way where the longer orders are left on the book, the more orders clients are able
     /// to add/cancel. After the error "EAPI:Rate limit exceeded", please wait ~15 min for
     /// being able to send new requests.
-    OrderRateLimit,
+    OrderRateLimit(String),
 
     /// Temporary lockout error messages can occur if you had too many failed API calls
     /// or too many invalid nonce errors in a short period of time or invalid signatures.
@@ -96,7 +226,7 @@ pub enum KError {
     /// several invalid nonce errors, please increase the nonce window as this can help
     /// reduce the frequency that these errors will occur. Please try to reduce the frequency
     /// of your private API calls also.
-    TemporaryLockout,
+    TemporaryLockout(String),
 
     /// Opening new spot positions on margin has been temporarily suspended for trading
     /// engine maintenance. The feature will be making a return soon and you can follow
@@ -105,7 +235,7 @@ pub enum KError {
     /// Another reasons may be that spot positions on margin are not currently available
     /// for clients residing in certain countries. Please see this article for our
     /// [geographical restrictions](https://support.kraken.com/hc/en-us/articles/360001368823)
-    OpenPosition,
+    OpenPosition(String),
 
     /// No [hedging](https://support.kraken.com/hc/en-us/articles/205367328-Hedging).
     /// Cannot open a long and short position for the same pair.
@@ -113,13 +243,13 @@ pub enum KError {
     /// If wishing to open a long and short position for the same currency, please
     /// choose different trading pairs with the same currency as the base or quote currency.
     /// Ex: short XBT/USD, long XBT/EUR.
-    OpposingPosition,
+    OpposingPosition(String),
 
     /// This error occurs when you have exceeded the margin allowance limits for your
     /// current verification level. Margin allowance limits for each currency varies
     /// based on your current verification level. Please refer to this support article for
     /// more information regarding [margin allowance limits](https://support.kraken.com/hc/en-us/articles/209238787-Margin-allowance-limits)
-    MarginAllowanceExceeded,
+    MarginAllowanceExceeded(String),
 
     /// We have limited funds available for margin extensions. The "insufficient margin"
     /// message indicates that we are out of funds in the applicable margin pool for the
@@ -127,16 +257,16 @@ pub enum KError {
     /// order just seconds or minutes later, but high volume orders and orders placed during
     /// high volume times may take longer. Please accept our apologies for any inconvenience.
     /// For more [information](https://support.kraken.com/hc/en-us/articles/217696017-Insufficient-Margin)
-    InsufficientMargin,
+    InsufficientMargin(String),
 
     /// You do not have the funds available to place this order. Please review your open
     /// positions and orders for items that may be holding up your funds
-    InsufficientFunds,
+    InsufficientFunds(String),
 
     /// You have not met the minimum order volume for this asset.
     ///
     /// You can find more information about [minimum order sizes](https://support.kraken.com/hc/en-us/articles/205893708-What-is-the-minimum-order-size-volume-)
-    OrderMinimum,
+    OrderMinimum(String),
 
     /// You have exceeded the maximum amount of open orders available to your account.
     ///
@@ -144,7 +274,7 @@ pub enum KError {
     /// orders or verify your account to a higher level.
     ///
     /// You can learn more about the [maximum amount of open orders](https://support.kraken.com/hc/en-us/articles/209090607-What-is-the-maximum-number-of-open-orders-positions-)
-    OrderLimit,
+    OrderLimit(String),
 
     /// You have exceeded the maximum amount of open positions available to your account.
     ///
@@ -152,41 +282,125 @@ pub enum KError {
     /// of your open positions or verify your account to a higher level if possible.
     ///
     /// You can learn more about the [maximum amount of open positions](https://support.kraken.com/hc/en-us/articles/209090607-What-is-the-maximum-number-of-open-orders-positions-)
-    PositionLimit,
+    PositionLimit(String),
 
     /// In case of this error you will need to submit your order with the following parameter:
     /// ‘trading_agreement’:’agree’
     ///
     /// This will resolve the error message you are receiving when placing an order:
     /// [Trading Agreement](https://support.kraken.com/hc/en-us/articles/360000920026-Trading-Agreement-required-for-orders-sent-via-API)
-    TradingAgreement,
+    TradingAgreement(String),
 
     /// The service errors you are experiencing should only be temporary. You may wish to
     /// resubmit your requests if they have failed. We will be monitoring the issues and
     /// will update our [page](https://status.kraken.com/)
-    ServiceUnavailable,
+    ServiceUnavailable(String),
 
     /// The service errors you are experiencing should only be temporary. You may wish to
     /// resubmit your requests if they have failed. We will be monitoring the issues and
     /// will update our [page](https://status.kraken.com/)
-    ServiceBusy,
+    ServiceBusy(String),
 
     /// When we are facing API degradation issues, these can translate into problems for
     /// both Kraken and cryptowat.ch in the form of service unavailable messages, 8XX errors
     /// on [cryptowatch](https://cryptowat.ch/) and site outages.
-    InternalError,
+    InternalError(String),
 
     /// This issue has to do with the security of your account which may have been compromised.
     /// Please change your password and Two-Factor Authentication and contact our Support Center
-    Locked,
+    Locked(String),
 
     /// This error occurs when a flag or input parameter is disabled temporary or permanently.
     /// The error should come from one of the inputs passed, please contact our support sending
     /// a log with the complete informations used for the call that generated the error
-    FeatureDisabled,
+    FeatureDisabled(String),
+
+    /// The requested method doesn't exist. Kraken probably removed or renamed the endpoint
+    UnknownMethod(String),
+
+    /// Too many requests were made in a short period of time
+    TooManyRequests(String),
+
+    /// The request was malformed or is not supported by the endpoint
+    BadRequest(String),
+
+    /// The requested asset doesn't exist or isn't available for the request
+    UnknownAsset(String),
+
+    /// The market only accepts order cancellations at the moment
+    MarketCancelOnly(String),
 
-    /// Default KError if none of the others above
-    UnknownError,
+    /// The market only accepts post only limit orders at the moment
+    MarketPostOnly(String),
+
+    /// The market only accepts limit orders at the moment
+    MarketLimitOnly(String),
+
+    /// The request was not processed before its deadline
+    DeadlineElapsed(String),
+
+    /// The account's margin level is too low to open the position
+    MarginLevelTooLow(String),
+
+    /// The position exceeds the maximum margin position size for the pair
+    MarginPositionSizeExceeded(String),
+
+    /// The order's cost (price times volume) is below the minimum for the pair
+    CostMinimum(String),
+
+    /// The order's price is not a multiple of the pair's tick size
+    TickSize(String),
+
+    /// The per domain limit on adding and canceling orders was exceeded
+    DomainRateLimit(String),
+
+    /// The referenced position doesn't exist
+    UnknownPosition(String),
+
+    /// The referenced order doesn't exist
+    UnknownOrder(String),
+
+    /// The order is invalid, such as an order type not supported by the pair
+    InvalidOrder(String),
+
+    /// The order's price is invalid, such as having too many decimals for the pair
+    InvalidPrice(String),
+
+    /// A reduce only order would open or increase a position
+    ReduceOnly(String),
+
+    /// The maximum number of scheduled orders was reached
+    ScheduledOrdersLimit(String),
+
+    /// The order would exceed the account's acquisition limits
+    AcquisitionLimit(String),
+
+    /// The trading request is invalid
+    InvalidRequest(String),
+
+    /// The withdrawal key doesn't exist. Withdrawal keys are set up in account management
+    UnknownWithdrawKey(String),
+
+    /// The amount to deposit, withdraw or transfer is invalid
+    InvalidAmount(String),
+
+    /// No more deposit addresses can be generated for the asset
+    TooManyAddresses(String),
+
+    /// The withdrawal fee is higher than the maximum fee allowed by the request
+    MaxFeeExceeded(String),
+
+    /// The referenced deposit, withdrawal or transfer doesn't exist
+    UnknownReferenceId(String),
+
+    /// The funding method doesn't exist or isn't available for the asset
+    NoFundingMethod(String),
+
+    /// The session is invalid or has expired
+    InvalidSession(String),
+
+    /// Any other error returned by Kraken. The raw error is kept so that it isn't lost
+    UnknownError(String),
 }
 
 impl fmt::Display for KError {
@@ -195,92 +409,602 @@ impl fmt::Display for KError {
             // Errors from internal dependencies
             KError::HttpError(err) => write!(f, "HTTP Error: {}", err.to_string()),
             KError::ParseError(err) => write!(f, "Parse Error: {}", err.to_string()),
+            KError::ResponseParseError(err, context) => {
+                write!(f, "Parse Error: {} ({})", err, context)
+            }
+            KError::HttpStatus(context) => write!(f, "HTTP Status Error: {}", context),
+            KError::NonJsonResponse(context) => write!(f, "Non-JSON Response: {}", context),
+            KError::ArchiveError(err) => write!(f, "Archive Error: {}", err),
+            KError::CsvError(err) => write!(f, "CSV Error: {}", err),
+            KError::RequestError(err) => write!(f, "Request Error: {}", err),
 
             // Errors from processing within this crate
-            KError::AssetParseError => write!(f, "Failed to parse string into KAsset"),
+            KError::NonceError(err) => write!(f, "Nonce Error: {}", err),
+            KError::SignerError(err) => write!(f, "Signer Error: {}", err),
+            KError::TlsError(err) => write!(f, "TLS Error: {}", err),
+            KError::MalformedKey => write!(f, "Malformed API Key"),
+            KError::MalformedSecret => write!(f, "Malformed API Secret"),
+            KError::MissingResult => write!(f, "Kraken Returned No Result"),
+            KError::NoAvailableKey => write!(f, "No Available Key In The Client Pool"),
+            KError::MissingCredentials(name) => write!(f, "Missing Credentials: {}", name),
+            KError::InsecureCredentialsFile(path) => write!(
+                f,
+                "Credentials File Is Accessible By Other Users: {}",
+                path.display()
+            ),
+            KError::CredentialsIoError(err) => write!(f, "Credentials Error: {}", err),
+            KError::KeyfileError => write!(f, "Failed To Encrypt Or Decrypt Keyfile"),
+            KError::InvalidOtpSecret => write!(f, "Invalid TOTP Secret"),
+            KError::AssetParseError(val) => {
+                write!(f, "Failed to parse {} into KAsset/KAssetPair", val)
+            }
+            KError::TimestampParseError => write!(f, "Failed to parse string into KTimestamp"),
+            KError::WithdrawalsDisabled => write!(f, "Withdrawals Are Disabled For This Client"),
+            KError::InvalidProxy(reason) => write!(f, "Invalid Proxy: {}", reason),
 
             // Errors coming directly from Kraken's servers
-            KError::UnknownAssetPair => write!(f, "Unknown AssetPair"),
-            KError::InvalidArguments => write!(f, "Invalid Arguments"),
-            KError::PermissionDenied => write!(f, "Permission Denied"),
-            KError::InvalidKey => write!(f, "Invalid Key"),
-            KError::InvalidSignature => write!(f, "Invalid Signature"),
-            KError::InvalidNonce => write!(f, "Invalid Nonce"),
-            KError::APIRateLimit => write!(f, "API Rate Limit"),
-            KError::OrderRateLimit => write!(f, "Order Rate Limit"),
-            KError::TemporaryLockout => write!(f, "Temporary Lockout"),
-            KError::OpenPosition => write!(f, "Cannot Open Position"),
-            KError::OpposingPosition => write!(f, "Cannot Open Opposing Position"),
-            KError::MarginAllowanceExceeded => write!(f, "Margin Allowance Exceeded"),
-            KError::InsufficientMargin => write!(f, "Insufficient Margin"),
-            KError::InsufficientFunds => write!(f, "Insufficient User Funds"),
-            KError::OrderMinimum => write!(f, "Order Minimum Not Met (volume too low)"),
-            KError::OrderLimit => write!(f, "Orders Limit Reached"),
-            KError::PositionLimit => write!(f, "Positions Limit Reached"),
-            KError::TradingAgreement => write!(f, "Trading Agreement Required"),
-            KError::ServiceUnavailable => write!(f, "Service Unavailable"),
-            KError::ServiceBusy => write!(f, "Service Busy"),
-            KError::InternalError => write!(f, "Internal Error"),
-            KError::Locked => write!(f, "Account Locked"),
-            KError::FeatureDisabled => write!(f, "A Feature Was Disabled"),
-            KError::UnknownError => write!(f, "An Unknown Error Occurred"),
+            KError::UnknownAssetPair(_) => write!(f, "Unknown AssetPair"),
+            KError::InvalidArguments(_) => write!(f, "Invalid Arguments"),
+            KError::PermissionDenied(_) => write!(f, "Permission Denied"),
+            KError::InvalidKey(_) => write!(f, "Invalid Key"),
+            KError::InvalidSignature(_) => write!(f, "Invalid Signature"),
+            KError::InvalidNonce(_) => write!(f, "Invalid Nonce"),
+            KError::APIRateLimit(_) => write!(f, "API Rate Limit"),
+            KError::OrderRateLimit(_) => write!(f, "Order Rate Limit"),
+            KError::TemporaryLockout(_) => write!(f, "Temporary Lockout"),
+            KError::OpenPosition(_) => write!(f, "Cannot Open Position"),
+            KError::OpposingPosition(_) => write!(f, "Cannot Open Opposing Position"),
+            KError::MarginAllowanceExceeded(_) => write!(f, "Margin Allowance Exceeded"),
+            KError::InsufficientMargin(_) => write!(f, "Insufficient Margin"),
+            KError::InsufficientFunds(_) => write!(f, "Insufficient User Funds"),
+            KError::OrderMinimum(_) => write!(f, "Order Minimum Not Met (volume too low)"),
+            KError::OrderLimit(_) => write!(f, "Orders Limit Reached"),
+            KError::PositionLimit(_) => write!(f, "Positions Limit Reached"),
+            KError::TradingAgreement(_) => write!(f, "Trading Agreement Required"),
+            KError::ServiceUnavailable(_) => write!(f, "Service Unavailable"),
+            KError::ServiceBusy(_) => write!(f, "Service Busy"),
+            KError::InternalError(_) => write!(f, "Internal Error"),
+            KError::Locked(_) => write!(f, "Account Locked"),
+            KError::FeatureDisabled(_) => write!(f, "A Feature Was Disabled"),
+            KError::UnknownMethod(_) => write!(f, "Unknown Method"),
+            KError::TooManyRequests(_) => write!(f, "Too Many Requests"),
+            KError::BadRequest(_) => write!(f, "Bad Request"),
+            KError::UnknownAsset(_) => write!(f, "Unknown Asset"),
+            KError::MarketCancelOnly(_) => write!(f, "Market In Cancel Only Mode"),
+            KError::MarketPostOnly(_) => write!(f, "Market In Post Only Mode"),
+            KError::MarketLimitOnly(_) => write!(f, "Market In Limit Only Mode"),
+            KError::DeadlineElapsed(_) => write!(f, "Deadline Elapsed"),
+            KError::MarginLevelTooLow(_) => write!(f, "Margin Level Too Low"),
+            KError::MarginPositionSizeExceeded(_) => write!(f, "Margin Position Size Exceeded"),
+            KError::CostMinimum(_) => write!(f, "Cost Minimum Not Met"),
+            KError::TickSize(_) => write!(f, "Tick Size Check Failed"),
+            KError::DomainRateLimit(_) => write!(f, "Domain Rate Limit"),
+            KError::UnknownPosition(_) => write!(f, "Unknown Position"),
+            KError::UnknownOrder(_) => write!(f, "Unknown Order"),
+            KError::InvalidOrder(_) => write!(f, "Invalid Order"),
+            KError::InvalidPrice(_) => write!(f, "Invalid Price"),
+            KError::ReduceOnly(_) => write!(f, "Reduce Only Order Would Increase A Position"),
+            KError::ScheduledOrdersLimit(_) => write!(f, "Scheduled Orders Limit Reached"),
+            KError::AcquisitionLimit(_) => write!(f, "Acquisition Limit Exceeded"),
+            KError::InvalidRequest(_) => write!(f, "Invalid Request"),
+            KError::UnknownWithdrawKey(_) => write!(f, "Unknown Withdraw Key"),
+            KError::InvalidAmount(_) => write!(f, "Invalid Amount"),
+            KError::TooManyAddresses(_) => write!(f, "Too Many Addresses"),
+            KError::MaxFeeExceeded(_) => write!(f, "Max Fee Exceeded"),
+            KError::UnknownReferenceId(_) => write!(f, "Unknown Reference Id"),
+            KError::NoFundingMethod(_) => write!(f, "No Funding Method"),
+            KError::InvalidSession(_) => write!(f, "Invalid Session"),
+            KError::UnknownError(raw) => write!(f, "An Unknown Error Occurred: {}", raw),
+        }
+    }
+}
+
+impl Error for KError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KError::HttpError(err) => Some(err),
+            KError::ParseError(err) | KError::ResponseParseError(err, _) => Some(err),
+            KError::ArchiveError(err) => Some(err),
+            KError::CsvError(err) => Some(err),
+            KError::RequestError(err) => Some(err),
+            KError::NonceError(err) | KError::SignerError(err) | KError::TlsError(err) => Some(err),
+            KError::CredentialsIoError(err) => Some(err),
+            _ => None,
         }
     }
 }
 
+impl KError {
+    /// Returns whether sending the same request again may succeed, possibly after waiting for
+    /// [suggested_backoff()][KError::suggested_backoff]
+    ///
+    /// Rate limits, lockouts, service outages, invalid nonces (a new nonce is assigned each time
+    /// a request is sent) and connections that failed before the request was sent are
+    /// retryable. Keep in mind that a request that reached Kraken may have been processed even
+    /// if an error was returned, so retried orders should carry a `userref` to find duplicates
+    pub fn is_retryable(&self) -> bool {
+        self.suggested_backoff().is_some()
+    }
+
+    /// Returns whether the error is caused by one of Kraken's rate limits or a lockout
+    pub fn is_rate_limit(&self) -> bool {
+        match self {
+            KError::APIRateLimit(_)
+            | KError::OrderRateLimit(_)
+            | KError::DomainRateLimit(_)
+            | KError::TooManyRequests(_)
+            | KError::TemporaryLockout(_) => true,
+            KError::HttpStatus(context) => context.status == 429,
+            _ => false,
+        }
+    }
+
+    /// Returns whether the error is caused by invalid, missing or insufficiently permitted
+    /// credentials
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(
+            self,
+            KError::InvalidKey(_)
+                | KError::InvalidSignature(_)
+                | KError::PermissionDenied(_)
+                | KError::InvalidSession(_)
+                | KError::Locked(_)
+                | KError::MalformedKey
+                | KError::MalformedSecret
+                | KError::MissingCredentials(_)
+                | KError::InsecureCredentialsFile(_)
+                | KError::KeyfileError
+                | KError::SignerError(_)
+                | KError::InvalidOtpSecret
+        )
+    }
+
+    /// Returns whether the error is caused by the funds or margin available, or by the way funds
+    /// are being moved
+    pub fn is_funds_related(&self) -> bool {
+        matches!(
+            self,
+            KError::InsufficientFunds(_)
+                | KError::InsufficientMargin(_)
+                | KError::MarginAllowanceExceeded(_)
+                | KError::MarginLevelTooLow(_)
+                | KError::MarginPositionSizeExceeded(_)
+                | KError::InvalidAmount(_)
+                | KError::MaxFeeExceeded(_)
+                | KError::UnknownWithdrawKey(_)
+                | KError::NoFundingMethod(_)
+                | KError::TooManyAddresses(_)
+                | KError::WithdrawalsDisabled
+        )
+    }
+
+    /// Returns whether retrying the request can't succeed without changing it or the client
+    pub fn is_fatal(&self) -> bool {
+        !self.is_retryable()
+    }
+
+    /// Returns how long to wait before sending the request again, or `None` if the request
+    /// shouldn't be retried
+    pub fn suggested_backoff(&self) -> Option<Duration> {
+        match self {
+            // Usually another client sharing the key has sent a higher nonce. Retrying right away
+            // tends to collide with it again
+            KError::InvalidNonce(_) => Some(Duration::from_secs(1)),
+            KError::HttpError(err) if err.is_connect() => Some(Duration::from_secs(1)),
+            KError::ServiceUnavailable(_)
+            | KError::ServiceBusy(_)
+            | KError::InternalError(_)
+            | KError::DeadlineElapsed(_)
+            | KError::NonJsonResponse(_) => Some(Duration::from_secs(5)),
+            KError::APIRateLimit(_) | KError::TooManyRequests(_) => Some(Duration::from_secs(15)),
+            KError::OrderRateLimit(_) | KError::DomainRateLimit(_) => Some(Duration::from_secs(60)),
+            KError::TemporaryLockout(_) => Some(Duration::from_secs(15 * 60)),
+            KError::HttpStatus(context) if context.status == 429 => Some(
+                context
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == "retry-after")
+                    .and_then(|(_, value)| value.parse().ok())
+                    .map_or(Duration::from_secs(15), Duration::from_secs),
+            ),
+            KError::HttpStatus(context) if context.status >= 500 => Some(Duration::from_secs(5)),
+            _ => None,
+        }
+    }
+
+    /// Returns the error string exactly as Kraken returned it, such as `EAPI:Invalid key`, or
+    /// `None` for errors raised within this crate
+    pub fn raw(&self) -> Option<&str> {
+        match self {
+            KError::UnknownAssetPair(raw)
+            | KError::InvalidArguments(raw)
+            | KError::PermissionDenied(raw)
+            | KError::InvalidKey(raw)
+            | KError::InvalidSignature(raw)
+            | KError::InvalidNonce(raw)
+            | KError::APIRateLimit(raw)
+            | KError::OrderRateLimit(raw)
+            | KError::TemporaryLockout(raw)
+            | KError::OpenPosition(raw)
+            | KError::OpposingPosition(raw)
+            | KError::MarginAllowanceExceeded(raw)
+            | KError::InsufficientMargin(raw)
+            | KError::InsufficientFunds(raw)
+            | KError::OrderMinimum(raw)
+            | KError::OrderLimit(raw)
+            | KError::PositionLimit(raw)
+            | KError::TradingAgreement(raw)
+            | KError::ServiceUnavailable(raw)
+            | KError::ServiceBusy(raw)
+            | KError::InternalError(raw)
+            | KError::Locked(raw)
+            | KError::FeatureDisabled(raw)
+            | KError::UnknownMethod(raw)
+            | KError::TooManyRequests(raw)
+            | KError::BadRequest(raw)
+            | KError::UnknownAsset(raw)
+            | KError::MarketCancelOnly(raw)
+            | KError::MarketPostOnly(raw)
+            | KError::MarketLimitOnly(raw)
+            | KError::DeadlineElapsed(raw)
+            | KError::MarginLevelTooLow(raw)
+            | KError::MarginPositionSizeExceeded(raw)
+            | KError::CostMinimum(raw)
+            | KError::TickSize(raw)
+            | KError::DomainRateLimit(raw)
+            | KError::UnknownPosition(raw)
+            | KError::UnknownOrder(raw)
+            | KError::InvalidOrder(raw)
+            | KError::InvalidPrice(raw)
+            | KError::ReduceOnly(raw)
+            | KError::ScheduledOrdersLimit(raw)
+            | KError::AcquisitionLimit(raw)
+            | KError::InvalidRequest(raw)
+            | KError::UnknownWithdrawKey(raw)
+            | KError::InvalidAmount(raw)
+            | KError::TooManyAddresses(raw)
+            | KError::MaxFeeExceeded(raw)
+            | KError::UnknownReferenceId(raw)
+            | KError::NoFundingMethod(raw)
+            | KError::InvalidSession(raw)
+            | KError::UnknownError(raw) => Some(raw),
+            _ => None,
+        }
+    }
+
+    /// Returns details of the response that caused the error, for errors raised because
+    /// Kraken's response couldn't be used
+    pub fn context(&self) -> Option<&KErrorContext> {
+        match self {
+            KError::ResponseParseError(_, context)
+            | KError::HttpStatus(context)
+            | KError::NonJsonResponse(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the category of an error returned by Kraken, or `None` for errors raised within
+    /// this crate
+    pub fn category(&self) -> Option<KErrorCategory> {
+        self.raw().map(KErrorCategory::from_raw)
+    }
+
+    /// Returns the name of the variant, such as `APIRateLimit`. Used to label errors in
+    /// [KrakenMetrics][crate::metrics::KrakenMetrics]
+    pub fn name(&self) -> &'static str {
+        match self {
+            KError::HttpError(_) => "HttpError",
+            KError::ParseError(_) => "ParseError",
+            KError::ResponseParseError(_, _) => "ResponseParseError",
+            KError::HttpStatus(_) => "HttpStatus",
+            KError::NonJsonResponse(_) => "NonJsonResponse",
+            KError::ArchiveError(_) => "ArchiveError",
+            KError::CsvError(_) => "CsvError",
+            KError::NonceError(_) => "NonceError",
+            KError::MalformedKey => "MalformedKey",
+            KError::MalformedSecret => "MalformedSecret",
+            KError::MissingCredentials(_) => "MissingCredentials",
+            KError::InsecureCredentialsFile(_) => "InsecureCredentialsFile",
+            KError::CredentialsIoError(_) => "CredentialsIoError",
+            KError::KeyfileError => "KeyfileError",
+            KError::SignerError(_) => "SignerError",
+            KError::RequestError(_) => "RequestError",
+            KError::MissingResult => "MissingResult",
+            KError::NoAvailableKey => "NoAvailableKey",
+            KError::InvalidOtpSecret => "InvalidOtpSecret",
+            KError::AssetParseError(_) => "AssetParseError",
+            KError::TimestampParseError => "TimestampParseError",
+            KError::WithdrawalsDisabled => "WithdrawalsDisabled",
+            KError::InvalidProxy(_) => "InvalidProxy",
+            KError::TlsError(_) => "TlsError",
+            KError::UnknownAssetPair(_) => "UnknownAssetPair",
+            KError::InvalidArguments(_) => "InvalidArguments",
+            KError::PermissionDenied(_) => "PermissionDenied",
+            KError::InvalidKey(_) => "InvalidKey",
+            KError::InvalidSignature(_) => "InvalidSignature",
+            KError::InvalidNonce(_) => "InvalidNonce",
+            KError::APIRateLimit(_) => "APIRateLimit",
+            KError::OrderRateLimit(_) => "OrderRateLimit",
+            KError::TemporaryLockout(_) => "TemporaryLockout",
+            KError::OpenPosition(_) => "OpenPosition",
+            KError::OpposingPosition(_) => "OpposingPosition",
+            KError::MarginAllowanceExceeded(_) => "MarginAllowanceExceeded",
+            KError::InsufficientMargin(_) => "InsufficientMargin",
+            KError::InsufficientFunds(_) => "InsufficientFunds",
+            KError::OrderMinimum(_) => "OrderMinimum",
+            KError::OrderLimit(_) => "OrderLimit",
+            KError::PositionLimit(_) => "PositionLimit",
+            KError::TradingAgreement(_) => "TradingAgreement",
+            KError::ServiceUnavailable(_) => "ServiceUnavailable",
+            KError::ServiceBusy(_) => "ServiceBusy",
+            KError::InternalError(_) => "InternalError",
+            KError::Locked(_) => "Locked",
+            KError::FeatureDisabled(_) => "FeatureDisabled",
+            KError::UnknownMethod(_) => "UnknownMethod",
+            KError::TooManyRequests(_) => "TooManyRequests",
+            KError::BadRequest(_) => "BadRequest",
+            KError::UnknownAsset(_) => "UnknownAsset",
+            KError::MarketCancelOnly(_) => "MarketCancelOnly",
+            KError::MarketPostOnly(_) => "MarketPostOnly",
+            KError::MarketLimitOnly(_) => "MarketLimitOnly",
+            KError::DeadlineElapsed(_) => "DeadlineElapsed",
+            KError::MarginLevelTooLow(_) => "MarginLevelTooLow",
+            KError::MarginPositionSizeExceeded(_) => "MarginPositionSizeExceeded",
+            KError::CostMinimum(_) => "CostMinimum",
+            KError::TickSize(_) => "TickSize",
+            KError::DomainRateLimit(_) => "DomainRateLimit",
+            KError::UnknownPosition(_) => "UnknownPosition",
+            KError::UnknownOrder(_) => "UnknownOrder",
+            KError::InvalidOrder(_) => "InvalidOrder",
+            KError::InvalidPrice(_) => "InvalidPrice",
+            KError::ReduceOnly(_) => "ReduceOnly",
+            KError::ScheduledOrdersLimit(_) => "ScheduledOrdersLimit",
+            KError::AcquisitionLimit(_) => "AcquisitionLimit",
+            KError::InvalidRequest(_) => "InvalidRequest",
+            KError::UnknownWithdrawKey(_) => "UnknownWithdrawKey",
+            KError::InvalidAmount(_) => "InvalidAmount",
+            KError::TooManyAddresses(_) => "TooManyAddresses",
+            KError::MaxFeeExceeded(_) => "MaxFeeExceeded",
+            KError::UnknownReferenceId(_) => "UnknownReferenceId",
+            KError::NoFundingMethod(_) => "NoFundingMethod",
+            KError::InvalidSession(_) => "InvalidSession",
+            KError::UnknownError(_) => "UnknownError",
+        }
+    }
+
+    // Map an error string returned by Kraken onto its KError. Kraken errors are formatted as
+    // "<severity><category>:<type>[:<extra info>]"
+    fn from_raw(raw: String) -> Self {
+        let mut parts = raw.splitn(3, ':');
+        let (category, kind) = match (parts.next(), parts.next()) {
+            (Some(category), Some(kind)) => (category, kind),
+            _ => return KError::UnknownError(raw),
+        };
+
+        match (category, kind) {
+            ("EQuery", "Unknown asset pair") => KError::UnknownAssetPair(raw),
+            ("EGeneral", "Invalid arguments") => KError::InvalidArguments(raw),
+            ("EGeneral", "Permission denied") => KError::PermissionDenied(raw),
+            ("EAPI", "Invalid key") => KError::InvalidKey(raw),
+            ("EAPI", "Invalid signature") => KError::InvalidSignature(raw),
+            ("EAPI", "Invalid nonce") => KError::InvalidNonce(raw),
+            ("EAPI", "Rate limit exceeded") => KError::APIRateLimit(raw),
+            ("EOrder", "Rate limit exceeded") => KError::OrderRateLimit(raw),
+            ("EGeneral", "Temporary lockout") => KError::TemporaryLockout(raw),
+            ("EOrder", "Cannot open position") => KError::OpenPosition(raw),
+            ("EOrder", "Cannot open opposing position") => KError::OpposingPosition(raw),
+            ("EOrder", "Margin allowance exceeded") => KError::MarginAllowanceExceeded(raw),
+            ("EOrder", "Insufficient margin") => KError::InsufficientMargin(raw),
+            ("EOrder" | "EFunding", "Insufficient funds") => KError::InsufficientFunds(raw),
+            ("EOrder", "Order minimum not met") => KError::OrderMinimum(raw),
+            ("EOrder", "Orders limit exceeded") => KError::OrderLimit(raw),
+            ("EOrder", "Positions limit exceeded") => KError::PositionLimit(raw),
+            ("EOrder", "Trading agreement required") => KError::TradingAgreement(raw),
+            ("EService", "Unavailable") => KError::ServiceUnavailable(raw),
+            ("EService", "Busy") => KError::ServiceBusy(raw),
+            ("EGeneral" | "EDatabase", "Internal error") => KError::InternalError(raw),
+            ("ETrade", "Locked") => KError::Locked(raw),
+            ("EAPI", "Feature disabled") => KError::FeatureDisabled(raw),
+            ("EGeneral", "Unknown method") => KError::UnknownMethod(raw),
+            ("EGeneral", "Too many requests") => KError::TooManyRequests(raw),
+            ("EAPI", "Bad request") => KError::BadRequest(raw),
+            ("EQuery" | "EFunding", "Unknown asset") => KError::UnknownAsset(raw),
+            ("EService", "Market in cancel_only mode") => KError::MarketCancelOnly(raw),
+            ("EService", "Market in post_only mode") => KError::MarketPostOnly(raw),
+            ("EService", "Market in limit_only mode") => KError::MarketLimitOnly(raw),
+            ("EService", "Deadline elapsed") => KError::DeadlineElapsed(raw),
+            ("EOrder", "Margin level too low") => KError::MarginLevelTooLow(raw),
+            ("EOrder", "Margin position size exceeded") => KError::MarginPositionSizeExceeded(raw),
+            ("EOrder", "Cost minimum not met") => KError::CostMinimum(raw),
+            ("EOrder", "Tick size check failed") => KError::TickSize(raw),
+            ("EOrder", "Domain rate limit exceeded") => KError::DomainRateLimit(raw),
+            ("EOrder", "Unknown position") => KError::UnknownPosition(raw),
+            ("EOrder", "Unknown order") => KError::UnknownOrder(raw),
+            ("EOrder", "Invalid order") => KError::InvalidOrder(raw),
+            ("EOrder", "Invalid price") => KError::InvalidPrice(raw),
+            ("EOrder", "Reduce only") => KError::ReduceOnly(raw),
+            ("EOrder", "Scheduled orders limit exceeded") => KError::ScheduledOrdersLimit(raw),
+            ("EBM", "limit exceeded") => KError::AcquisitionLimit(raw),
+            ("ETrade", "Invalid request") => KError::InvalidRequest(raw),
+            ("EFunding", "Unknown withdraw key") => KError::UnknownWithdrawKey(raw),
+            ("EFunding", "Invalid amount") => KError::InvalidAmount(raw),
+            ("EFunding", "Too many addresses") => KError::TooManyAddresses(raw),
+            ("EFunding", "Max fee exceeded") => KError::MaxFeeExceeded(raw),
+            ("EFunding", "Unknown reference id") => KError::UnknownReferenceId(raw),
+            ("EFunding", "No funding method") => KError::NoFundingMethod(raw),
+            ("ESession", "Invalid session") => KError::InvalidSession(raw),
+            _ => KError::UnknownError(raw),
+        }
+    }
+}
+
+/// Details of the request and response that led to an error
+#[derive(Clone, Debug)]
+pub struct KErrorContext {
+    /// Endpoint that was requested, such as `Balance`
+    pub endpoint: String,
+    /// Whether the endpoint is `public` or `private`
+    pub method: String,
+    /// Http status code of the response
+    pub status: u16,
+    /// Response headers useful for tracking down where the response came from, such as
+    /// `content-type`, `server` and `cf-ray`
+    pub headers: Vec<(String, String)>,
+    /// Start of the response body, lossily converted to utf-8 and truncated to at most
+    /// [BODY_LIMIT][KErrorContext::BODY_LIMIT] bytes
+    pub body: String,
+}
+
+impl KErrorContext {
+    /// Maximum number of bytes of the response body kept in the context
+    pub const BODY_LIMIT: usize = 512;
+
+    pub(crate) const HEADERS: [&'static str; 6] = [
+        "content-type",
+        "content-length",
+        "server",
+        "date",
+        "retry-after",
+        "cf-ray",
+    ];
+
+    pub(crate) fn truncate_body(body: &[u8]) -> String {
+        let mut body = String::from_utf8_lossy(body).into_owned();
+        if body.len() > KErrorContext::BODY_LIMIT {
+            let mut end = KErrorContext::BODY_LIMIT;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            body.truncate(end);
+            body.push_str("...");
+        }
+        body
+    }
+}
+
+impl fmt::Display for KErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} returned {}: {}",
+            self.method, self.endpoint, self.status, self.body
+        )
+    }
+}
+
+/// Category of an error or warning returned by Kraken, taken from the prefix of the raw string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KErrorCategory {
+    /// General errors such as invalid arguments or permission problems (`EGeneral`)
+    General,
+    /// Authentication and rate limiting errors (`EAPI`)
+    API,
+    /// Errors querying market data (`EQuery`)
+    Query,
+    /// Errors placing or managing orders (`EOrder`)
+    Order,
+    /// Errors from the trading engine (`ETrade`)
+    Trade,
+    /// Errors depositing, withdrawing or transferring funds (`EFunding`)
+    Funding,
+    /// Kraken's service is unavailable or degraded (`EService`)
+    Service,
+    /// Session errors (`ESession`)
+    Session,
+    /// Database errors (`EDatabase`)
+    Database,
+    /// Any other category. Holds the category without its severity code
+    Other(String),
+}
+
+impl KErrorCategory {
+    fn from_raw(raw: &str) -> Self {
+        let prefix = raw.split(':').next().unwrap_or_default();
+        // Strip the severity code, 'E' for errors and 'W' for warnings
+        match prefix.strip_prefix(['E', 'W']).unwrap_or(prefix) {
+            "General" => KErrorCategory::General,
+            "API" => KErrorCategory::API,
+            "Query" => KErrorCategory::Query,
+            "Order" => KErrorCategory::Order,
+            "Trade" => KErrorCategory::Trade,
+            "Funding" => KErrorCategory::Funding,
+            "Service" => KErrorCategory::Service,
+            "Session" => KErrorCategory::Session,
+            "Database" => KErrorCategory::Database,
+            other => KErrorCategory::Other(other.to_string()),
+        }
+    }
+}
+
+/// Warning returned by Kraken alongside a successful result
+///
+/// Warnings share the format of errors but start with a `W` severity code instead of an `E`. See
+/// [request_with_warnings()][super::client::KrakenClient::request_with_warnings]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KWarning {
+    raw: String,
+}
+
+impl KWarning {
+    /// Returns the warning string exactly as Kraken returned it
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the category of the warning
+    pub fn category(&self) -> KErrorCategory {
+        KErrorCategory::from_raw(&self.raw)
+    }
+}
+
+impl fmt::Display for KWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
 impl From<HyperError> for KrakenErrors<KError> {
     fn from(err: HyperError) -> Self {
         KrakenErrors(vec![KError::HttpError(err)])
     }
 }
 
+impl From<HttpRequestError> for KrakenErrors<KError> {
+    fn from(err: HttpRequestError) -> Self {
+        KrakenErrors(vec![KError::RequestError(err)])
+    }
+}
+
 impl From<SerdeError> for KrakenErrors<KError> {
     fn from(err: SerdeError) -> Self {
         KrakenErrors(vec![KError::ParseError(err)])
     }
 }
 
-pub(crate) fn generate_errors(errors: Vec<String>) -> KrakenErrors<KError> {
-    let mut errs: Vec<KError> = Vec::with_capacity(errors.len());
-    for error in errors {
-        let index = error.find(':').unwrap();
-        // Assume kraken will not return an error like "EError:" with no error description
-        let (category, message) = error.split_at(index + 1);
-
-        let err = match message {
-            "Unknown asset pair" => KError::UnknownAssetPair,
-            "Invalid arguments" => KError::InvalidArguments,
-            "Permission denied" => KError::PermissionDenied,
-            "Invalid key" => KError::InvalidKey,
-            "Invalid signature" => KError::InvalidSignature,
-            "Invalid nonce" => KError::InvalidNonce,
-            "Rate limit exceeded" => match category {
-                "EAPI:" => KError::APIRateLimit,
-                "EOrder:" => KError::OrderRateLimit,
-                _ => KError::UnknownError,
-            },
-            "Temporary lockout" => KError::TemporaryLockout,
-            "Cannot open position" => KError::OpenPosition,
-            "Cannot open opposing position" => KError::OpposingPosition,
-            "Margin allowance exceeded" => KError::MarginAllowanceExceeded,
-            "Insufficient margin" => KError::InsufficientMargin,
-            "Insufficient insufficient user funds" => KError::InsufficientFunds,
-            "Order minimum not volume too low" => KError::OrderMinimum,
-            "Orders limit exceeded" => KError::OrderLimit,
-            "Positions limit exceeded" => KError::PositionLimit,
-            "Trading agreement required" => KError::TradingAgreement,
-            "Unavailable" => KError::ServiceUnavailable,
-            "Busy" => KError::ServiceBusy,
-            "Internal error" => KError::InternalError,
-            "Locked" => KError::Locked,
-            "Feature disabled" => KError::FeatureDisabled,
-            _ => KError::UnknownError,
-        };
+impl From<ZipError> for KrakenErrors<KError> {
+    fn from(err: ZipError) -> Self {
+        KrakenErrors(vec![KError::ArchiveError(err)])
+    }
+}
 
-        errs.push(err);
+impl From<CsvError> for KrakenErrors<KError> {
+    fn from(err: CsvError) -> Self {
+        KrakenErrors(vec![KError::CsvError(err)])
     }
+}
+
+pub(crate) fn generate_errors(errors: Vec<String>) -> KrakenErrors<KError> {
+    KrakenErrors(errors.into_iter().map(KError::from_raw).collect())
+}
 
-    KrakenErrors(errs)
+// Separate the warnings Kraken returns in its error list from the actual errors
+pub(crate) fn split_warnings(entries: Vec<String>) -> (Vec<String>, Vec<KWarning>) {
+    let (warnings, errors): (Vec<String>, Vec<String>) =
+        entries.into_iter().partition(|entry| entry.starts_with('W'));
+    (
+        errors,
+        warnings.into_iter().map(|raw| KWarning { raw }).collect(),
+    )
 }
 
 #[cfg(test)]
@@ -288,10 +1012,69 @@ mod tests {
     use super::*;
     #[test]
     fn display_kraken_errors() {
-        let errors = KrakenErrors(vec![KError::UnknownAssetPair, KError::UnknownError]);
+        let errors = KrakenErrors(vec![
+            KError::UnknownAssetPair(String::from("EQuery:Unknown asset pair")),
+            KError::UnknownError(String::from("EGeneral:Something new")),
+        ]);
         let empty_errors = KrakenErrors(vec![]);
 
-        assert_eq!(format!("{}", errors), String::from("[Unknown AssetPair,An Unknown Error Occurred]"));
+        assert_eq!(
+            format!("{}", errors),
+            String::from("[Unknown AssetPair,An Unknown Error Occurred: EGeneral:Something new]")
+        );
         assert_eq!(format!("{}", empty_errors), String::from("[]"));
     }
+
+    #[test]
+    fn classify_errors() {
+        let lockout = KError::TemporaryLockout(String::from("EGeneral:Temporary lockout"));
+        let funds = KError::InsufficientFunds(String::from("EOrder:Insufficient funds"));
+        let busy = KError::ServiceBusy(String::from("EService:Busy"));
+
+        assert!(lockout.is_rate_limit() && lockout.is_retryable());
+        assert!(funds.is_funds_related() && funds.is_fatal());
+        assert!(KError::InvalidKey(String::from("EAPI:Invalid key")).is_auth_failure());
+        assert_eq!(KrakenErrors(vec![busy]).suggested_backoff(), Some(Duration::from_secs(5)));
+        assert_eq!(
+            KrakenErrors(vec![KError::ServiceBusy(String::new()), lockout]).suggested_backoff(),
+            Some(Duration::from_secs(15 * 60))
+        );
+        assert!(KrakenErrors(vec![KError::ServiceBusy(String::new()), funds]).is_fatal());
+        assert_eq!(
+            KError::InvalidNonce(String::from("EAPI:Invalid nonce")).suggested_backoff(),
+            Some(Duration::from_secs(1))
+        );
+
+        let errors = KrakenErrors::from(serde_json::from_str::<u32>("x").unwrap_err());
+        assert!(errors.source().unwrap().is::<SerdeError>());
+
+        // Pairs that can't be parsed locally are not mistaken for errors returned by Kraken
+        let errors = "WXYZABCDEF1".parse::<crate::api::asset::KAssetPair>().unwrap_err().0;
+        assert!(matches!(&errors[..], [KError::AssetParseError(pair)] if pair == "WXYZABCDEF1"));
+        assert_eq!((errors[0].raw(), errors[0].category()), (None, None));
+    }
+
+    #[test]
+    fn generate_kraken_errors() {
+        let (errors, warnings) = split_warnings(vec![
+            String::from("EOrder:Insufficient funds"),
+            String::from("EOrder:Order minimum not met"),
+            String::from("EGeneral:Invalid arguments:volume"),
+            String::from("EOrder:Rate limit exceeded"),
+            String::from("Kraken is down"),
+            String::from("WGeneral:Deprecated parameter"),
+        ]);
+        let errors = generate_errors(errors).0;
+
+        assert!(matches!(errors[0], KError::InsufficientFunds(_)));
+        assert!(matches!(errors[1], KError::OrderMinimum(_)));
+        assert!(matches!(errors[2], KError::InvalidArguments(_)));
+        assert!(matches!(errors[3], KError::OrderRateLimit(_)));
+        assert!(matches!(errors[4], KError::UnknownError(_)));
+        assert_eq!(errors[2].raw(), Some("EGeneral:Invalid arguments:volume"));
+        assert_eq!(errors[3].category(), Some(KErrorCategory::Order));
+        assert_eq!(errors[4].category(), Some(KErrorCategory::Other(String::from("Kraken is down"))));
+        assert_eq!(warnings[0].category(), KErrorCategory::General);
+        assert_eq!(warnings[0].raw(), "WGeneral:Deprecated parameter");
+    }
 }
@@ -21,9 +21,15 @@
 //!   type and convert it to a [KrakenInput][api::KrakenInput] by calling
 //!   [finish()][api::Input::finish] or [finish_clone()][api::Input::finish_clone]
 //!   which exist for every [input][api::Input] type
-//! - You must await the call to request
-//! - Deserializing the data returned from Kraken into output structs is done for you. Currently if
-//!   you pass in the wrong [ouput][api::Output] type, the parsing will fail
+//! - You must await the call to request, unless using the synchronous `KrakenBlockingClient`
+//!   enabled by the `blocking` feature
+//! - Deserializing the data returned from Kraken into output structs is done for you. Every
+//!   [KrakenInput][api::KrakenInput] carries the [ouput][api::Output] type of its endpoint, so
+//!   asking for the wrong output type doesn't compile. [send()][client::KrakenClient::send]
+//!   accepts input builders directly and infers the output type
+//! - Endpoints without a builder in this crate can be reached with
+//!   [KICustom][api::custom::KICustom], and the raw json of any response is available through
+//!   [request_value()][client::KrakenClient::request_value]
 //! - Builder methods require ownership so if you must perform some application logic while
 //!   building a [KrakenInput][api::KrakenInput] you must reassign the variable like so:
 //!
@@ -55,6 +61,21 @@
 //!     `clear_asset_list()` exist to **remove** the previous asset list from the request builder
 //! - The above design allows for templating your requests. You can `clone()` a templated request 
 //!   and then change only the data you care about before sending the request. 
+//! - Input builders serialize to and from the map of parameters sent to Kraken, so templates can
+//!   be stored in config files and loaded with serde before calling `finish()`. Parameters the
+//!   builder can't set are rejected when loading
+//! - The `tracing` feature instruments every request with a [tracing](https://docs.rs/tracing)
+//!   span recording the endpoint, method, attempt, http status, latency and any Kraken error
+//!   codes. `API-Key`, `API-Sign`, `otp` and withdrawal keys are never logged. Nonces are
+//!   logged at trace level and a [KrakenClientPool][pool::KrakenClientPool] logs keys it sets
+//!   aside. Neither the client nor the pool waits for Kraken's rate limit to recover, they
+//!   return the rate limit error instead, so there are no events for rate limit waits
+//! - Request counts, latency histograms, error counters and the estimated rate limit counter can
+//!   be recorded with a [KrakenMetrics][metrics::KrakenMetrics] registry and exported in the
+//!   Prometheus text format
+//! - Connections can go through an HTTP `CONNECT` or SOCKS5 [proxy][proxy::Proxy], with optional
+//!   authentication. TLS uses native-tls by default; enable the `rustls` feature with
+//!   `default-features = false` for builds without OpenSSL
 //! # Examples
 //! See <https://www.kraken.com/features/api#example-api-code-php-lib> for more info on these
 //! examples
@@ -107,7 +128,7 @@
 //!     .validate(true)
 //!     .finish();
 //!
-//! // Valid credentials to be entered above, otherwise this will panic
+//! // Valid credentials to be entered above, otherwise this will return an error
 //! // let add_order_output = client.request::<KOAddOrder>(&add_order_input).await?;
 //! # let add_order_output = String::from("");
 //!
@@ -122,8 +143,22 @@
 
 pub mod api;
 mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+mod connector;
+pub mod credentials;
 pub mod error;
+pub mod metrics;
+pub mod middleware;
+#[cfg(test)]
+mod mock;
+pub mod nonce;
+pub mod otp;
+pub mod pool;
+pub mod proxy;
+pub mod signer;
+mod trace;
 
 pub use api::private;
 pub use api::public;
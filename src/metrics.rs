@@ -0,0 +1,422 @@
+//! Module containing in-process metrics of the requests a [KrakenClient] makes
+//!
+//! A [KrakenMetrics] registry attached with [set_metrics()][KrakenClient::set_metrics] counts the
+//! requests sent to each endpoint, records how long they took in latency histograms, counts the
+//! errors returned by [variant][KError::name] and estimates the call counter Kraken uses to
+//! enforce [APIRateLimit][KError::APIRateLimit]. The registry is cheap to clone and every clone
+//! reads the same metrics, so one clone can be attached to the client while another is read or
+//! exported in the Prometheus text format
+//!
+//! ```
+//! # use kraapi::client::KrakenClient;
+//! # use kraapi::metrics::KrakenMetrics;
+//! # use kraapi::pool::RateTier;
+//! let mut metrics = KrakenMetrics::new();
+//! metrics.set_tier(RateTier::Intermediate);
+//!
+//! let mut client = KrakenClient::new("", "");
+//! client.set_metrics(metrics.clone());
+//!
+//! // Later, e.g. in the handler of a /metrics endpoint
+//! let exposition = metrics.to_prometheus();
+//! ```
+//!
+//! The call counter is estimated for a single API key, so clients using different keys, such as
+//! the keys of a [KrakenClientPool][crate::pool::KrakenClientPool], should each be given their
+//! own registry
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::api::{KrakenResult, MethodType};
+use crate::error::KError;
+use crate::pool::{self, RateTier};
+
+#[cfg(doc)]
+use crate::client::KrakenClient;
+
+/// Shared registry of request metrics | See the [module documentation][self]
+#[derive(Clone)]
+pub struct KrakenMetrics {
+    registry: Arc<Mutex<Registry>>,
+}
+
+struct Registry {
+    tier: RateTier,
+    buckets: Vec<Duration>,
+    requests: BTreeMap<String, u64>,
+    latency: BTreeMap<String, Histogram>,
+    errors: BTreeMap<(String, &'static str), u64>,
+    counter: f64,
+    updated: Instant,
+}
+
+impl Registry {
+    // Bring the estimated call counter up to date with the decay since it was last updated
+    fn decay(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.counter = (self.counter - elapsed * self.tier.decay()).max(0.0);
+        self.updated = now;
+    }
+}
+
+impl KrakenMetrics {
+    /// Default upper bounds of the buckets of the latency histograms
+    pub const DEFAULT_BUCKETS: [Duration; 10] = [
+        Duration::from_millis(25),
+        Duration::from_millis(50),
+        Duration::from_millis(100),
+        Duration::from_millis(250),
+        Duration::from_millis(500),
+        Duration::from_secs(1),
+        Duration::from_millis(2500),
+        Duration::from_secs(5),
+        Duration::from_secs(10),
+        Duration::from_secs(30),
+    ];
+
+    /// Construct a new, empty registry estimating the call counter of a key on the
+    /// [Starter][RateTier::Starter] tier
+    pub fn new() -> Self {
+        KrakenMetrics {
+            registry: Arc::new(Mutex::new(Registry {
+                tier: RateTier::Starter,
+                buckets: KrakenMetrics::DEFAULT_BUCKETS.to_vec(),
+                requests: BTreeMap::new(),
+                latency: BTreeMap::new(),
+                errors: BTreeMap::new(),
+                counter: 0.0,
+                updated: Instant::now(),
+            })),
+        }
+    }
+
+    /// Set the verification tier of the account, which sets how the call counter is estimated
+    ///
+    /// Defaults to [Starter][RateTier::Starter]
+    pub fn set_tier(&mut self, tier: RateTier) {
+        let mut registry = self.registry();
+        registry.decay(Instant::now());
+        registry.tier = tier;
+    }
+
+    /// Set the upper bounds of the buckets of the latency histograms, clearing the latencies
+    /// recorded so far
+    ///
+    /// Defaults to [DEFAULT_BUCKETS][KrakenMetrics::DEFAULT_BUCKETS]
+    pub fn set_buckets(&mut self, mut buckets: Vec<Duration>) {
+        buckets.sort();
+        buckets.dedup();
+        let mut registry = self.registry();
+        registry.buckets = buckets;
+        registry.latency.clear();
+    }
+
+    /// Returns the number of requests made to `endpoint`, e.g. `AddOrder`
+    pub fn requests(&self, endpoint: &str) -> u64 {
+        self.registry()
+            .requests
+            .get(endpoint)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the latency histogram of the requests made to `endpoint`, if any were made
+    pub fn latency(&self, endpoint: &str) -> Option<Histogram> {
+        self.registry().latency.get(endpoint).cloned()
+    }
+
+    /// Returns the number of errors of the variant named `name`, e.g. `ServiceBusy`, returned by
+    /// any endpoint. See [KError::name]
+    pub fn errors(&self, name: &str) -> u64 {
+        self.registry()
+            .errors
+            .iter()
+            .filter(|((_, error), _)| *error == name)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Returns the estimated value of the call counter of the API key. Kraken answers with
+    /// [APIRateLimit][KError::APIRateLimit] once it exceeds
+    /// [rate_limit_max()][KrakenMetrics::rate_limit_max]
+    pub fn rate_limit_counter(&self) -> f64 {
+        let mut registry = self.registry();
+        registry.decay(Instant::now());
+        registry.counter
+    }
+
+    /// Returns the maximum of the call counter for the tier of the account
+    pub fn rate_limit_max(&self) -> f64 {
+        self.registry().tier.max_counter()
+    }
+
+    /// Export the metrics in the Prometheus text exposition format
+    ///
+    /// | Metric | Type | Labels |
+    /// |--------|------|--------|
+    /// | `kraapi_requests_total` | counter | `endpoint` |
+    /// | `kraapi_request_duration_seconds` | histogram | `endpoint` |
+    /// | `kraapi_errors_total` | counter | `endpoint`, `error` |
+    /// | `kraapi_rate_limit_counter` | gauge | |
+    /// | `kraapi_rate_limit_max` | gauge | |
+    pub fn to_prometheus(&self) -> String {
+        let mut registry = self.registry();
+        registry.decay(Instant::now());
+        let mut out = String::new();
+
+        out.push_str("# HELP kraapi_requests_total Requests made to each Kraken endpoint\n");
+        out.push_str("# TYPE kraapi_requests_total counter\n");
+        for (endpoint, count) in &registry.requests {
+            let _ = writeln!(
+                out,
+                "kraapi_requests_total{{endpoint=\"{}\"}} {}",
+                escape(endpoint),
+                count
+            );
+        }
+
+        out.push_str(
+            "# HELP kraapi_request_duration_seconds Latency of requests to each Kraken endpoint\n",
+        );
+        out.push_str("# TYPE kraapi_request_duration_seconds histogram\n");
+        for (endpoint, histogram) in &registry.latency {
+            let endpoint = escape(endpoint);
+            for (bound, count) in histogram.buckets() {
+                let _ = writeln!(
+                    out,
+                    "kraapi_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"{}\"}} {}",
+                    endpoint,
+                    bound.as_secs_f64(),
+                    count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "kraapi_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"+Inf\"}} {}",
+                endpoint,
+                histogram.count()
+            );
+            let _ = writeln!(
+                out,
+                "kraapi_request_duration_seconds_sum{{endpoint=\"{}\"}} {}",
+                endpoint,
+                histogram.sum().as_secs_f64()
+            );
+            let _ = writeln!(
+                out,
+                "kraapi_request_duration_seconds_count{{endpoint=\"{}\"}} {}",
+                endpoint,
+                histogram.count()
+            );
+        }
+
+        out.push_str("# HELP kraapi_errors_total Errors returned by each Kraken endpoint\n");
+        out.push_str("# TYPE kraapi_errors_total counter\n");
+        for ((endpoint, error), count) in &registry.errors {
+            let _ = writeln!(
+                out,
+                "kraapi_errors_total{{endpoint=\"{}\",error=\"{}\"}} {}",
+                escape(endpoint),
+                error,
+                count
+            );
+        }
+
+        out.push_str("# HELP kraapi_rate_limit_counter Estimated call counter of the API key\n");
+        out.push_str("# TYPE kraapi_rate_limit_counter gauge\n");
+        let _ = writeln!(out, "kraapi_rate_limit_counter {}", registry.counter);
+        out.push_str("# HELP kraapi_rate_limit_max Maximum call counter of the API key\n");
+        out.push_str("# TYPE kraapi_rate_limit_max gauge\n");
+        let _ = writeln!(out, "kraapi_rate_limit_max {}", registry.tier.max_counter());
+        out
+    }
+
+    // Record a request to `endpoint` that took `elapsed` and ended with `result`
+    pub(crate) fn record<R>(
+        &self,
+        endpoint: &str,
+        method: MethodType,
+        elapsed: Duration,
+        result: &KrakenResult<R>,
+    ) {
+        let mut guard = self.registry();
+        let registry = &mut *guard;
+        *registry.requests.entry(endpoint.to_string()).or_default() += 1;
+        registry
+            .latency
+            .entry(endpoint.to_string())
+            .or_insert_with(|| Histogram::new(&registry.buckets))
+            .observe(elapsed);
+
+        let errors = result
+            .as_ref()
+            .err()
+            .map_or(&[][..], |errors| &errors.0[..]);
+        for error in errors {
+            *registry
+                .errors
+                .entry((endpoint.to_string(), error.name()))
+                .or_default() += 1;
+        }
+
+        // Requests refused before reaching Kraken don't count against the call counter
+        let reached_kraken = errors.is_empty() || errors.iter().any(|error| error.raw().is_some());
+        if method == MethodType::Private && reached_kraken {
+            registry.decay(Instant::now());
+            let max = registry.tier.max_counter();
+            registry.counter = match errors
+                .iter()
+                .any(|error| matches!(error, KError::APIRateLimit(_)))
+            {
+                true => max,
+                false => (registry.counter + pool::call_cost(endpoint)).min(max),
+            };
+        }
+    }
+
+    fn registry(&self) -> MutexGuard<'_, Registry> {
+        self.registry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for KrakenMetrics {
+    fn default() -> Self {
+        KrakenMetrics::new()
+    }
+}
+
+/// Latency histogram of the requests made to one endpoint
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    bounds: Vec<Duration>,
+    // Observations falling in each bucket, with one more for those above the last bound
+    counts: Vec<u64>,
+    sum: Duration,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &[Duration]) -> Self {
+        Histogram {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len() + 1],
+            sum: Duration::ZERO,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, elapsed: Duration) {
+        let bucket = self.bounds.partition_point(|bound| *bound < elapsed);
+        self.counts[bucket] += 1;
+        self.sum += elapsed;
+        self.count += 1;
+    }
+
+    /// Returns the number of requests recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total time taken by the requests recorded
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// Returns the average time taken by the requests recorded, if there are any
+    pub fn mean(&self) -> Option<Duration> {
+        match self.count {
+            0 => None,
+            count => Some(self.sum.div_f64(count as f64)),
+        }
+    }
+
+    /// Iterator over the upper bound of each bucket and the number of requests that took at most
+    /// that long. Requests slower than the last bound are only included in
+    /// [count()][Histogram::count]
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.bounds
+            .iter()
+            .zip(self.counts.iter().scan(0, |total, count| {
+                *total += count;
+                Some(*total)
+            }))
+            .map(|(bound, total)| (*bound, total))
+    }
+}
+
+// Escape a label value as required by the Prometheus text format
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::KrakenErrors;
+
+    #[test]
+    fn record_requests() {
+        let mut metrics = KrakenMetrics::new();
+        metrics.set_buckets(vec![Duration::from_millis(100), Duration::from_secs(1)]);
+
+        let ok: KrakenResult<()> = Ok(());
+        let busy: KrakenResult<()> = Err(KrakenErrors(vec![KError::ServiceBusy(String::from(
+            "EService:Busy",
+        ))]));
+        let refused: KrakenResult<()> = Err(KrakenErrors(vec![KError::WithdrawalsDisabled]));
+        metrics.record(
+            "Ledgers",
+            MethodType::Private,
+            Duration::from_millis(40),
+            &ok,
+        );
+        metrics.record(
+            "AddOrder",
+            MethodType::Private,
+            Duration::from_millis(300),
+            &busy,
+        );
+        metrics.record("Time", MethodType::Public, Duration::from_secs(2), &ok);
+        metrics.record("Withdraw", MethodType::Private, Duration::ZERO, &refused);
+
+        assert_eq!(metrics.requests("Ledgers"), 1);
+        assert_eq!(metrics.requests("Balance"), 0);
+        assert_eq!(metrics.errors("ServiceBusy"), 1);
+        assert_eq!(metrics.errors("WithdrawalsDisabled"), 1);
+        let latency = metrics.latency("AddOrder").unwrap();
+        assert_eq!(
+            latency.buckets().collect::<Vec<_>>(),
+            [(Duration::from_millis(100), 0), (Duration::from_secs(1), 1)]
+        );
+        assert_eq!(latency.mean(), Some(Duration::from_millis(300)));
+        // Ledgers costs 2 and AddOrder nothing. The refused withdrawal never reached Kraken
+        let counter = metrics.rate_limit_counter();
+        assert!(counter > 1.9 && counter <= 2.0);
+
+        let exposition = metrics.clone().to_prometheus();
+        for line in [
+            "kraapi_requests_total{endpoint=\"AddOrder\"} 1",
+            "kraapi_request_duration_seconds_bucket{endpoint=\"Time\",le=\"1\"} 0",
+            "kraapi_request_duration_seconds_bucket{endpoint=\"Time\",le=\"+Inf\"} 1",
+            "kraapi_request_duration_seconds_sum{endpoint=\"Time\"} 2",
+            "kraapi_errors_total{endpoint=\"AddOrder\",error=\"ServiceBusy\"} 1",
+            "kraapi_rate_limit_max 15",
+        ] {
+            assert!(exposition.lines().any(|l| l == line), "missing {}", line);
+        }
+
+        let limited: KrakenResult<()> = Err(KrakenErrors(vec![KError::APIRateLimit(
+            String::from("EAPI:Rate limit exceeded"),
+        )]));
+        metrics.record("Balance", MethodType::Private, Duration::ZERO, &limited);
+        assert!(metrics.rate_limit_counter() > 14.9);
+    }
+}
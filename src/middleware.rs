@@ -0,0 +1,249 @@
+//! Module containing the middleware that can observe and modify every request a
+//! [KrakenClient][super::client::KrakenClient] makes
+//!
+//! Middleware is registered with [add_middleware()][super::client::KrakenClient::add_middleware]
+//! and sees each request at three points:
+//!
+//! 1. [before_sign()][Middleware::before_sign] with the parameters of the request, before the
+//!    nonce and two factor password are added and the request is signed
+//! 2. [after_sign()][Middleware::after_sign] with the url, headers and form encoded body about to
+//!    be sent
+//! 3. [on_response()][Middleware::on_response] with the status, headers and body returned by
+//!    Kraken, before they are parsed
+//!
+//! Requests pass through the middleware in the order it was registered and responses in the
+//! reverse order, so the first middleware registered is the first to see the request and the last
+//! to see the response. Any hook can abort the request by returning an error
+//!
+//! ```
+//! # use kraapi::api::KrakenResult;
+//! # use kraapi::client::KrakenClient;
+//! # use kraapi::middleware::{Middleware, RawResponse, SignedRequest};
+//! # use http::HeaderValue;
+//! struct Audit;
+//!
+//! impl Middleware for Audit {
+//!     fn after_sign(&self, request: &mut SignedRequest) -> KrakenResult<()> {
+//!         request
+//!             .headers_mut()
+//!             .insert("X-Audit-Id", HeaderValue::from_static("nightly-report"));
+//!         Ok(())
+//!     }
+//!
+//!     fn on_response(&self, response: &mut RawResponse) -> KrakenResult<()> {
+//!         eprintln!("{} answered {}", response.endpoint(), response.status());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut client = KrakenClient::new("", "");
+//! client.add_middleware(Audit);
+//! ```
+
+use http::{HeaderMap, Method, StatusCode};
+use hyper::body::Bytes;
+use indexmap::map::IndexMap;
+use std::fmt;
+
+use crate::api::{KrakenResult, MethodType};
+use crate::trace::{RedactedHeaders, RedactedParams};
+
+/// Observes and modifies the requests made by a [KrakenClient][super::client::KrakenClient] and
+/// the responses to them. Every hook does nothing by default
+pub trait Middleware: Send + Sync {
+    /// Called with the parameters of the request before it is signed. Changes to the parameters
+    /// are included in the signature
+    fn before_sign(&self, request: &mut UnsignedRequest) -> KrakenResult<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called with the request about to be sent, after it has been signed. Headers can be
+    /// added or changed freely but the body can't change without invalidating the signature
+    fn after_sign(&self, request: &mut SignedRequest) -> KrakenResult<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called with the response to the request before it is parsed
+    fn on_response(&self, response: &mut RawResponse) -> KrakenResult<()> {
+        let _ = response;
+        Ok(())
+    }
+}
+
+/// Request that hasn't been signed yet | See [Middleware::before_sign]
+pub struct UnsignedRequest {
+    pub(crate) endpoint: String,
+    pub(crate) method: MethodType,
+    pub(crate) params: IndexMap<String, String>,
+    pub(crate) lists: &'static [&'static str],
+}
+
+// The Debug implementations hide the two factor password, withdrawal key, API key and signature
+impl fmt::Debug for UnsignedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnsignedRequest")
+            .field("endpoint", &self.endpoint)
+            .field("method", &self.method)
+            .field("params", &RedactedParams(&self.params))
+            .finish()
+    }
+}
+
+impl UnsignedRequest {
+    /// Name of the endpoint the request is sent to, e.g. `AddOrder`
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Whether the endpoint is public or private
+    pub fn method(&self) -> MethodType {
+        self.method
+    }
+
+    /// Value of the parameter `key`
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// Iterator over the parameters in the order they are sent
+    pub fn param_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Set the parameter `key` to `value`, overwriting any previous value
+    pub fn set_param(&mut self, key: &str, value: &str) {
+        self.params.insert(String::from(key), String::from(value));
+    }
+
+    /// Remove the parameter `key` from the request, returning its value
+    pub fn remove_param(&mut self, key: &str) -> Option<String> {
+        self.params.shift_remove(key)
+    }
+}
+
+/// Request about to be sent to Kraken | See [Middleware::after_sign]
+pub struct SignedRequest {
+    pub(crate) endpoint: String,
+    pub(crate) method: MethodType,
+    pub(crate) http_method: Method,
+    pub(crate) uri: String,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+}
+
+// The body is left out as it holds the same secrets as the parameters
+impl fmt::Debug for SignedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedRequest")
+            .field("endpoint", &self.endpoint)
+            .field("method", &self.method)
+            .field("http_method", &self.http_method)
+            .field("uri", &self.uri)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .finish_non_exhaustive()
+    }
+}
+
+impl SignedRequest {
+    /// Name of the endpoint the request is sent to, e.g. `AddOrder`
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Whether the endpoint is public or private
+    pub fn method(&self) -> MethodType {
+        self.method
+    }
+
+    /// The http method of the request
+    pub fn http_method(&self) -> &Method {
+        &self.http_method
+    }
+
+    /// The full url of the request, including the query of public requests
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The headers of the request, including `API-Key` and `API-Sign` for private requests
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Mutable access to the headers of the request
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// The form encoded body of the request. Empty for public requests
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+/// Response from Kraken that hasn't been parsed yet | See [Middleware::on_response]
+pub struct RawResponse {
+    pub(crate) endpoint: String,
+    pub(crate) method: MethodType,
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+impl fmt::Debug for RawResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawResponse")
+            .field("endpoint", &self.endpoint)
+            .field("method", &self.method)
+            .field("status", &self.status)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+impl RawResponse {
+    /// Name of the endpoint that responded, e.g. `AddOrder`
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Whether the endpoint is public or private
+    pub fn method(&self) -> MethodType {
+        self.method
+    }
+
+    /// The http status of the response
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Replace the http status of the response
+    pub fn set_status(&mut self, status: StatusCode) {
+        self.status = status;
+    }
+
+    /// The headers of the response
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Mutable access to the headers of the response
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// The body of the response, usually json
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Replace the body of the response
+    pub fn set_body<B: Into<Vec<u8>>>(&mut self, body: B) {
+        self.body = Bytes::from(body.into());
+    }
+}
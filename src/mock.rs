@@ -0,0 +1,33 @@
+// Minimal http server standing in for Kraken in tests
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// Serve every connection with the status, content type and body returned by `respond`, which
+// is given the lowercased request. Returns the url to point a KrakenClient at
+pub(crate) async fn serve<F>(respond: F) -> String
+where
+    F: Fn(&str) -> (u16, &'static str, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let len = stream.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..len]).to_lowercase();
+
+            let (status, content_type, body) = respond(&request);
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                content_type,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    url
+}
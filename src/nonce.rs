@@ -0,0 +1,265 @@
+//! Module containing the sources of nonces used to sign requests to private endpoints
+//!
+//! Kraken requires every nonce sent with an API key to be larger than the last one it accepted
+//! for that key. A [KrakenClient][super::client::KrakenClient] uses an [AtomicNonce] by default,
+//! which is enough when a single process owns the key. When several processes share a key, give
+//! each of their clients a [FileNonce] or a [CounterNonce] pointing at the same file with
+//! [set_nonce_source()][super::client::KrakenClient::set_nonce_source]
+
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Source of strictly increasing nonces
+///
+/// Implementations must never return the same nonce twice or a nonce smaller than one they
+/// previously returned, including across every client and process sharing the API key
+pub trait NonceSource: Send + Sync {
+    /// Return the next nonce
+    fn next_nonce(&self) -> io::Result<u64>;
+}
+
+// Current Unix time in microseconds
+fn now() -> u64 {
+    let duration = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    (duration.as_secs() * 1_000_000u64) + u64::from(duration.subsec_micros())
+}
+
+// Parse a persisted nonce. Empty files are treated as never having issued a nonce
+fn read_nonce(file: &mut File) -> io::Result<u64> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut contents)?;
+    match contents.trim() {
+        "" => Ok(0),
+        nonce => nonce
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+    }
+}
+
+fn write_nonce(file: &mut File, nonce: u64) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", nonce)?;
+    file.sync_data()
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// In-memory nonce source based on the current Unix time in microseconds
+///
+/// Never goes backwards, even if the system clock does or two nonces are requested within the
+/// same microsecond. Only suitable when a single process uses the API key
+#[derive(Debug, Default)]
+pub struct AtomicNonce {
+    last: AtomicU64,
+}
+
+impl AtomicNonce {
+    /// Construct a new AtomicNonce
+    pub fn new() -> Self {
+        AtomicNonce {
+            last: AtomicU64::new(0),
+        }
+    }
+}
+
+impl NonceSource for AtomicNonce {
+    fn next_nonce(&self) -> io::Result<u64> {
+        let now = now();
+        let previous = self
+            .last
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(now.max(last + 1))
+            })
+            .unwrap_or_else(|last| last);
+        Ok(now.max(previous + 1))
+    }
+}
+
+/// File backed nonce source based on the current Unix time in microseconds
+///
+/// The last issued nonce is stored in a file which is exclusively locked (advisory lock) while
+/// each nonce is issued, so every process pointing at the same file shares one strictly
+/// increasing sequence. Each nonce costs a file lock, read and write
+#[derive(Debug)]
+pub struct FileNonce {
+    path: PathBuf,
+}
+
+impl FileNonce {
+    /// Construct a new FileNonce storing the last issued nonce at `path`. The file is created if
+    /// it doesn't exist
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        open(path.as_ref())?;
+        Ok(FileNonce {
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+}
+
+impl NonceSource for FileNonce {
+    fn next_nonce(&self) -> io::Result<u64> {
+        let mut file = open(&self.path)?;
+        file.lock_exclusive()?;
+        let nonce = read_nonce(&mut file).map(|last| now().max(last + 1));
+        let res = nonce.and_then(|nonce| write_nonce(&mut file, nonce).map(|_| nonce));
+        FileExt::unlock(&file)?;
+        res
+    }
+}
+
+/// Counter based nonce source seeded from a persisted high-water mark
+///
+/// Nonces are a plain counter rather than a timestamp, so clock adjustments have no effect.
+/// Instead of persisting every nonce, blocks of nonces are reserved by writing the end of the
+/// block to the file before any nonce in it is issued, so a nonce is never reused even if the
+/// process crashes. Each nonce is still issued under an exclusive advisory lock on the file,
+/// after checking the high-water mark: if another process has reserved a block since, counting
+/// jumps above it so the sequence shared by every process never goes backwards. Compared to
+/// [FileNonce] this saves a write per nonce rather than the lock and read
+#[derive(Debug)]
+pub struct CounterNonce {
+    path: PathBuf,
+    block: u64,
+    // (next nonce to issue, end of the reserved block)
+    state: Mutex<(u64, u64)>,
+}
+
+impl CounterNonce {
+    /// Default number of nonces reserved per write to the high-water mark file
+    pub const DEFAULT_BLOCK: u64 = 1_000;
+
+    /// Construct a new CounterNonce persisting its high-water mark at `path`. The file is
+    /// created if it doesn't exist, in which case counting starts from the current Unix time in
+    /// microseconds so that nonces previously sent with the key are not reused
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        CounterNonce::with_block(path, CounterNonce::DEFAULT_BLOCK)
+    }
+
+    /// Construct a new CounterNonce reserving `block` nonces per write to the high-water mark
+    /// file. Smaller blocks waste fewer nonces on restart at the cost of more writes
+    pub fn with_block<P: AsRef<Path>>(path: P, block: u64) -> io::Result<Self> {
+        open(path.as_ref())?;
+        Ok(CounterNonce {
+            path: path.as_ref().to_path_buf(),
+            block: block.max(1),
+            state: Mutex::new((0, 0)),
+        })
+    }
+
+    // Issue the next nonce of the reserved block, first reserving a new block above the
+    // persisted high-water mark if the current one is used up or the mark has moved past it. The
+    // file must be locked
+    fn issue(&self, file: &mut File, state: &mut (u64, u64)) -> io::Result<u64> {
+        let mark = read_nonce(file)?;
+        let (next, end) = *state;
+        if next == 0 || next > end || mark != end {
+            let start = if mark == 0 { now() } else { mark.max(end) + 1 };
+            let end = start + self.block - 1;
+            write_nonce(file, end)?;
+            *state = (start, end);
+        }
+
+        let nonce = state.0;
+        state.0 += 1;
+        Ok(nonce)
+    }
+}
+
+impl NonceSource for CounterNonce {
+    fn next_nonce(&self) -> io::Result<u64> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut file = open(&self.path)?;
+        file.lock_exclusive()?;
+        let nonce = self.issue(&mut file, &mut state);
+        FileExt::unlock(&file)?;
+        nonce
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("kraapi-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn assert_increasing(source: &dyn NonceSource) {
+        let nonces: Vec<u64> = (0..100).map(|_| source.next_nonce().unwrap()).collect();
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn atomic_nonce() {
+        assert_increasing(&AtomicNonce::new());
+    }
+
+    #[test]
+    fn file_nonce() {
+        let path = temp_path("file-nonce");
+        fs::write(&path, (now() + 10_000_000).to_string()).unwrap();
+
+        let first = FileNonce::new(&path).unwrap();
+        let second = FileNonce::new(&path).unwrap();
+        assert_increasing(&first);
+        // A second source sharing the file continues the sequence, even though the persisted
+        // nonce is ahead of the clock
+        let last = read_nonce(&mut open(&path).unwrap()).unwrap();
+        assert_eq!(second.next_nonce().unwrap(), last + 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn counter_nonce() {
+        let path = temp_path("counter-nonce");
+        fs::write(&path, "41").unwrap();
+
+        let counter = CounterNonce::with_block(&path, 10).unwrap();
+        assert_eq!(counter.next_nonce().unwrap(), 42);
+        assert_increasing(&counter);
+
+        // A restarted counter resumes above every nonce the previous one could have issued
+        let issued = counter.next_nonce().unwrap();
+        let restarted = CounterNonce::with_block(&path, 10).unwrap();
+        assert!(restarted.next_nonce().unwrap() > issued);
+
+        // Counters sharing the file never go back below a nonce the other one issued, even
+        // though each still has nonces left in its own block
+        let first = CounterNonce::with_block(&path, 10).unwrap();
+        let second = CounterNonce::with_block(&path, 10).unwrap();
+        let mut last = first.next_nonce().unwrap();
+        for _ in 0..25 {
+            for counter in [&second, &first] {
+                let nonce = counter.next_nonce().unwrap();
+                assert!(nonce > last);
+                last = nonce;
+            }
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
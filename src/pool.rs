@@ -0,0 +1,445 @@
+//! Module containing a pool of clients that spreads requests over several API keys
+//!
+//! Kraken limits the rate of calls per API key with a call counter that each request increases
+//! and that decays over time. A [KrakenClientPool] holds several keys, each tagged with a name
+//! and a [KeyRole], and tracks the call counter of each key. Reporting requests are sent with
+//! query keys and order management with trading keys, so heavy reporting never uses up the call
+//! counter of a key that needs to trade
+//!
+//! ```no_run
+//! # use kraapi::credentials::{Credentials, FileCredentials};
+//! # use kraapi::pool::{KeyRole, KrakenClientPool};
+//! # use kraapi::private::account_balance::KIAccountBalance;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut pool = KrakenClientPool::new();
+//! pool.add_key("reports", KeyRole::Query, &FileCredentials::new("reports.key"))?;
+//! pool.add_key("reports-2", KeyRole::Query, &FileCredentials::new("reports-2.key"))?;
+//! pool.add_key("trading", KeyRole::Trading, &FileCredentials::new("trading.key"))?;
+//!
+//! // Sent with whichever reporting key has the lowest call counter
+//! let balance = pool.send(KIAccountBalance::build()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::api::{Endpoint, KrakenInput, KrakenResult, MethodType, Output};
+use crate::client::{self, KrakenClient, KrakenResponse};
+use crate::credentials::CredentialProvider;
+use crate::error::{KError, KWarning, KrakenErrors};
+use crate::private::retrieve_export::KORetrieveExport;
+use crate::trace;
+
+/// Which requests a key in a [KrakenClientPool] is used for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyRole {
+    /// Requests that only report on the account, such as `Balance`, `Ledgers` and
+    /// `TradesHistory`. These keys only need Kraken's query and export permissions
+    Query,
+    /// Every other private request, such as `AddOrder` and `CancelOrder`
+    Trading,
+}
+
+/// Verification tier of the account, which sets how quickly the call counter of its keys
+/// decays and how high it can go
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateTier {
+    /// Maximum counter of 15, decreasing by 0.33 per second
+    Starter,
+    /// Maximum counter of 20, decreasing by 0.5 per second
+    Intermediate,
+    /// Maximum counter of 20, decreasing by 1 per second
+    Pro,
+}
+
+impl RateTier {
+    pub(crate) fn max_counter(&self) -> f64 {
+        match self {
+            RateTier::Starter => 15.0,
+            RateTier::Intermediate | RateTier::Pro => 20.0,
+        }
+    }
+
+    pub(crate) fn decay(&self) -> f64 {
+        match self {
+            RateTier::Starter => 0.33,
+            RateTier::Intermediate => 0.5,
+            RateTier::Pro => 1.0,
+        }
+    }
+}
+
+/// Current state of a key in a [KrakenClientPool]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyStatus {
+    /// The key is in use. Holds the estimated value of its call counter
+    Available(f64),
+    /// Kraken temporarily locked the key out. It is used again once the lockout cooldown has
+    /// passed
+    LockedOut,
+    /// Kraken rejected the key as invalid. It is never used again
+    Invalid,
+}
+
+struct KeyState {
+    counter: f64,
+    updated: Instant,
+    locked_until: Option<Instant>,
+    invalid: bool,
+}
+
+impl KeyState {
+    // Bring the counter up to date with the decay since it was last updated
+    fn decay(&mut self, tier: RateTier, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.counter = (self.counter - elapsed * tier.decay()).max(0.0);
+        self.updated = now;
+    }
+
+    fn available(&self, now: Instant) -> bool {
+        let locked = self.locked_until.is_some_and(|until| until > now);
+        !self.invalid && !locked
+    }
+}
+
+struct PooledKey {
+    tag: String,
+    role: KeyRole,
+    client: KrakenClient,
+    state: Mutex<KeyState>,
+}
+
+impl PooledKey {
+    fn state(&self) -> MutexGuard<'_, KeyState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Pool of [KrakenClient]s, one per API key, that routes each request to a key with the
+/// matching [KeyRole]
+///
+/// Private requests are sent with the available key of their role that has the lowest call
+/// counter. When Kraken answers that a key is invalid or temporarily locked out, the key is taken
+/// out of rotation and the request is sent again with the next key of the same role. Public
+/// requests are sent with any available key
+pub struct KrakenClientPool {
+    keys: Vec<PooledKey>,
+    tier: RateTier,
+    lockout: Duration,
+}
+
+impl KrakenClientPool {
+    /// Default time a key is kept out of rotation after Kraken temporarily locks it out
+    pub const DEFAULT_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+    /// Construct a new, empty KrakenClientPool for an account on the
+    /// [Starter][RateTier::Starter] tier
+    pub fn new() -> Self {
+        KrakenClientPool {
+            keys: Vec::new(),
+            tier: RateTier::Starter,
+            lockout: KrakenClientPool::DEFAULT_LOCKOUT,
+        }
+    }
+
+    /// Set the verification tier of the account, which sets how call counters are estimated
+    ///
+    /// Defaults to [Starter][RateTier::Starter]
+    pub fn set_tier(&mut self, tier: RateTier) {
+        self.tier = tier;
+    }
+
+    /// Set how long a key is kept out of rotation after Kraken temporarily locks it out
+    ///
+    /// Defaults to [DEFAULT_LOCKOUT][KrakenClientPool::DEFAULT_LOCKOUT]
+    pub fn set_lockout_cooldown(&mut self, cooldown: Duration) {
+        self.lockout = cooldown;
+    }
+
+    /// Add a key with the credentials loaded by `provider`, tagged with the name `tag`
+    ///
+    /// The credentials are checked as they are in [KrakenClient::try_new]
+    pub fn add_key<P: CredentialProvider>(
+        &mut self,
+        tag: &str,
+        role: KeyRole,
+        provider: &P,
+    ) -> KrakenResult<()> {
+        self.add_client(tag, role, KrakenClient::from_provider(provider)?);
+        Ok(())
+    }
+
+    /// Add an already configured client, such as one using an external
+    /// [Signer][crate::signer::Signer], tagged with the name `tag`
+    pub fn add_client(&mut self, tag: &str, role: KeyRole, client: KrakenClient) {
+        self.keys.push(PooledKey {
+            tag: tag.to_string(),
+            role,
+            client,
+            state: Mutex::new(KeyState {
+                counter: 0.0,
+                updated: Instant::now(),
+                locked_until: None,
+                invalid: false,
+            }),
+        });
+    }
+
+    /// Returns the tags of the keys in this pool with the given role
+    pub fn tags(&self, role: KeyRole) -> Vec<&str> {
+        self.keys
+            .iter()
+            .filter(|key| key.role == role)
+            .map(|key| key.tag.as_str())
+            .collect()
+    }
+
+    /// Returns the current status of the key tagged `tag`, if it is in this pool
+    pub fn status(&self, tag: &str) -> Option<KeyStatus> {
+        let now = Instant::now();
+        self.keys.iter().find(|key| key.tag == tag).map(|key| {
+            let mut state = key.state();
+            state.decay(self.tier, now);
+            if state.invalid {
+                KeyStatus::Invalid
+            } else if !state.available(now) {
+                KeyStatus::LockedOut
+            } else {
+                KeyStatus::Available(state.counter)
+            }
+        })
+    }
+
+    /// Make a request with the key matching the endpoint of `input`. See
+    /// [KrakenClient::request] for how the input and output types relate
+    pub async fn request<T>(&self, input: &KrakenInput<T>) -> KrakenResult<T>
+    where
+        T: Output + DeserializeOwned,
+    {
+        self.request_with_warnings(input)
+            .await
+            .map(|(result, _)| result)
+    }
+
+    /// Make a request in the same way as [request()][KrakenClientPool::request], also returning
+    /// the warnings Kraken sent alongside the result. See [KrakenClient::request_with_warnings]
+    pub async fn request_with_warnings<T>(
+        &self,
+        input: &KrakenInput<T>,
+    ) -> KrakenResult<(T, Vec<KWarning>)>
+    where
+        T: Output + DeserializeOwned,
+    {
+        self.route(input, |response| client::parse_response(&response))
+            .await
+    }
+
+    /// Send an input builder or a finished [KrakenInput] with the key matching its endpoint. See
+    /// [KrakenClient::send]
+    pub async fn send<E>(&self, endpoint: E) -> KrakenResult<E::Output>
+    where
+        E: Endpoint,
+        E::Output: DeserializeOwned,
+    {
+        self.request(&endpoint.into_input()).await
+    }
+
+    /// Make a request with the key matching the endpoint of `input`, returning the result as
+    /// untyped json. See [KrakenClient::request_value]
+    pub async fn request_value<O>(&self, input: &KrakenInput<O>) -> KrakenResult<Value> {
+        self.route(input, |response| client::parse_response::<Value>(&response))
+            .await
+            .map(|(result, _)| result)
+    }
+
+    /// Retrieve a processed export report with one of the query keys. See
+    /// [KrakenClient::request_export]
+    pub async fn request_export(
+        &self,
+        input: &KrakenInput<KORetrieveExport>,
+    ) -> KrakenResult<KORetrieveExport> {
+        self.route(input, client::parse_export).await
+    }
+
+    async fn route<O, R, F>(&self, input: &KrakenInput<O>, parse: F) -> KrakenResult<R>
+    where
+        F: Fn(KrakenResponse) -> KrakenResult<R>,
+    {
+        let role = match input.info().method() {
+            MethodType::Public => None,
+            MethodType::Private if input.info().is_query() => Some(KeyRole::Query),
+            MethodType::Private => Some(KeyRole::Trading),
+        };
+        let cost = call_cost(input.info().endpoint());
+
+        let mut tried = Vec::new();
+        let mut last_error = None;
+        while let Some(index) = self.next_key(role, cost, &tried) {
+            tried.push(index);
+            let key = &self.keys[index];
+
+            let attempt = tried.len() as u32;
+            let res = key.client.execute(input, attempt, &parse).await;
+            match res {
+                Err(KrakenErrors(errors)) => {
+                    if !self.record_errors(key, &errors) {
+                        return Err(KrakenErrors(errors));
+                    }
+                    last_error = Some(KrakenErrors(errors));
+                }
+                res => return res,
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            trace::log_warn!(endpoint = %input.info().endpoint(), "no key available for the request");
+            KrakenErrors(vec![KError::NoAvailableKey])
+        }))
+    }
+
+    // Pick the available key of `role` with the lowest call counter that hasn't been tried yet
+    // and charge it for the request
+    fn next_key(&self, role: Option<KeyRole>, cost: f64, tried: &[usize]) -> Option<usize> {
+        let now = Instant::now();
+        let mut best: Option<(usize, f64)> = None;
+        for (index, key) in self.keys.iter().enumerate() {
+            if tried.contains(&index) || role.is_some_and(|role| key.role != role) {
+                continue;
+            }
+
+            let mut state = key.state();
+            if !state.available(now) {
+                continue;
+            }
+            state.decay(self.tier, now);
+            if !best.is_some_and(|(_, counter)| counter <= state.counter) {
+                best = Some((index, state.counter));
+            }
+        }
+
+        // Public requests don't count against the call counter
+        let (index, _) = best?;
+        if role.is_some() {
+            let mut state = self.keys[index].state();
+            state.counter = (state.counter + cost).min(self.tier.max_counter());
+        }
+        Some(index)
+    }
+
+    // Update the state of `key` from the errors Kraken answered with. Returns whether the request
+    // should be sent again with another key
+    fn record_errors(&self, key: &PooledKey, errors: &[KError]) -> bool {
+        let mut state = key.state();
+        let mut failover = false;
+        for error in errors {
+            match error {
+                KError::InvalidKey(_) => {
+                    trace::log_warn!(key = %key.tag, "key rejected as invalid, trying another key");
+                    state.invalid = true;
+                    failover = true;
+                }
+                KError::TemporaryLockout(_) => {
+                    trace::log_warn!(
+                        key = %key.tag,
+                        wait_ms = self.lockout.as_millis() as u64,
+                        "key locked out, skipping it until the lockout ends"
+                    );
+                    state.locked_until = Some(Instant::now() + self.lockout);
+                    failover = true;
+                }
+                KError::APIRateLimit(_) => {
+                    trace::log_warn!(key = %key.tag, "call counter of key exceeded");
+                    state.counter = self.tier.max_counter();
+                }
+                _ => {}
+            }
+        }
+        failover
+    }
+}
+
+impl Default for KrakenClientPool {
+    fn default() -> Self {
+        KrakenClientPool::new()
+    }
+}
+
+// Amount a request increases the call counter of its key by. Orders are limited by a separate
+// per pair counter in the trading engine
+pub(crate) fn call_cost(endpoint: &str) -> f64 {
+    match endpoint {
+        "Ledgers" | "QueryLedgers" | "TradesHistory" | "QueryTrades" => 2.0,
+        "AddOrder" | "CancelOrder" => 0.0,
+        _ => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::Credentials;
+    use crate::mock;
+    use crate::private::account_balance::{KIAccountBalance, KOAccountBalance};
+    use crate::private::cancel_all_orders::{KICancelAllOrders, KOCancelAllOrders};
+
+    // Answer each request based on the API key it was sent with
+    async fn mock_kraken() -> String {
+        mock::serve(|request| {
+            let body = if request.contains("api-key: invalid") {
+                r#"{"error":["EAPI:Invalid key"]}"#
+            } else if request.contains("api-key: locked") {
+                r#"{"error":["EGeneral:Temporary lockout"]}"#
+            } else if request.contains("/cancelall") && !request.contains("api-key: trading") {
+                r#"{"error":["EGeneral:Permission denied"]}"#
+            } else if request.contains("/cancelall") {
+                r#"{"error":[],"result":{"count":1}}"#
+            } else {
+                r#"{"error":[],"result":{"ZUSD":"1.0"}}"#
+            };
+            (200, "application/json", body.to_string())
+        })
+        .await
+    }
+
+    fn client(url: &str, key: &str) -> KrakenClient {
+        let mut client = KrakenClient::from_provider(&Credentials::new(key, "c2VjcmV0")).unwrap();
+        client.set_url(url);
+        client
+    }
+
+    #[tokio::test]
+    async fn routing_and_failover() {
+        let url = mock_kraken().await;
+        let mut pool = KrakenClientPool::new();
+        pool.add_client("invalid", KeyRole::Query, client(&url, "invalid"));
+        pool.add_client("locked", KeyRole::Query, client(&url, "locked"));
+        pool.add_client("query", KeyRole::Query, client(&url, "query"));
+        pool.add_client("trading", KeyRole::Trading, client(&url, "trading"));
+
+        let balance = pool
+            .request::<KOAccountBalance>(&KIAccountBalance::build())
+            .await
+            .unwrap();
+        assert_eq!(balance.balances.len(), 1);
+        assert_eq!(pool.status("invalid"), Some(KeyStatus::Invalid));
+        assert_eq!(pool.status("locked"), Some(KeyStatus::LockedOut));
+        assert!(
+            matches!(pool.status("query"), Some(KeyStatus::Available(counter)) if counter > 0.0)
+        );
+        assert_eq!(pool.status("trading"), Some(KeyStatus::Available(0.0)));
+
+        // Trading requests never use the query keys
+        let cancel = pool
+            .request::<KOCancelAllOrders>(&KICancelAllOrders::build())
+            .await
+            .unwrap();
+        assert_eq!(cancel.count, 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Module containing the ways requests to private endpoints can be signed
+//!
+//! Every request to a private endpoint carries an `API-Sign` header computed from the API secret.
+//! By default a [KrakenClient][super::client::KrakenClient] signs requests itself with an
+//! [HmacSigner] holding the secret. To keep the secret out of the process making the requests,
+//! give the client a [UnixSocketSigner] with
+//! [set_signer()][super::client::KrakenClient::set_signer] and run a signing daemon holding the
+//! secret, such as the reference `kraken-signer` binary built with this crate
+//!
+//! # Signing daemon protocol
+//!
+//! For each request the [UnixSocketSigner] opens a new connection to the daemon and writes a
+//! single line of json `{"path": "...", "nonce": "...", "params": "..."}`, where `params` is the
+//! form encoded request body. The daemon answers with a single line of json holding either
+//! `{"signature": "..."}` or `{"error": "..."}` and closes the connection. [serve()] implements
+//! the daemon side of the protocol around any [Signer]
+
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use crate::credentials::Secret;
+use crate::error::{KError, KrakenErrors};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Computes the `API-Sign` header sent with every request to a private endpoint
+///
+/// Signing is run on tokio's blocking thread pool, so implementations are free to block, e.g.
+/// while waiting on a signing daemon
+pub trait Signer: Send + Sync {
+    /// Sign the request sent to the url `path` (e.g. `/0/private/Balance`) with the given
+    /// `nonce` and the form encoded body `params`, which already contains the nonce. Returns the
+    /// base64 encoded signature
+    fn sign(&self, path: &str, nonce: &str, params: &str) -> Result<String, KrakenErrors<KError>>;
+
+    /// Check that the signer is able to sign requests without sending anything to Kraken
+    ///
+    /// Defaults to doing nothing, in which case problems are only reported when signing
+    fn validate(&self) -> Result<(), KrakenErrors<KError>> {
+        Ok(())
+    }
+}
+
+/// Signs requests in process with HMAC-SHA512 as described by Kraken
+///
+/// This is the default signer of a [KrakenClient][super::client::KrakenClient]
+#[derive(Debug)]
+pub struct HmacSigner {
+    secret: Secret,
+}
+
+impl HmacSigner {
+    /// Construct a new HmacSigner from the base64 encoded API secret
+    pub fn new<S: Into<Secret>>(secret: S) -> Self {
+        HmacSigner {
+            secret: secret.into(),
+        }
+    }
+
+    fn decoded_secret(&self) -> Result<Zeroizing<Vec<u8>>, KrakenErrors<KError>> {
+        match base64::decode(self.secret.expose()) {
+            Ok(secret) if !secret.is_empty() => Ok(Zeroizing::new(secret)),
+            _ => Err(KrakenErrors(vec![KError::MalformedSecret])),
+        }
+    }
+}
+
+impl Signer for HmacSigner {
+    fn sign(&self, path: &str, nonce: &str, params: &str) -> Result<String, KrakenErrors<KError>> {
+        let api_secret = self.decoded_secret()?;
+        // Use base64 decoded API key as the HMAC key with Sha512 as the hashing function
+        let mut hmac = HmacSha512::new_from_slice(&api_secret)
+            .map_err(|_| KrakenErrors(vec![KError::MalformedSecret]))?;
+        let mut sha256 = Sha256::new();
+
+        // SHA256(nonce + POST data)
+        sha256.update(nonce.as_bytes());
+        sha256.update(params.as_bytes());
+        let sha_res = sha256.finalize();
+
+        hmac.update(path.as_bytes());
+        hmac.update(&sha_res);
+
+        Ok(base64::encode(hmac.finalize().into_bytes()))
+    }
+
+    fn validate(&self) -> Result<(), KrakenErrors<KError>> {
+        self.decoded_secret().map(|_| ())
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignRequest {
+    path: String,
+    nonce: String,
+    params: String,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum SignResponse {
+    Signature(String),
+    Error(String),
+}
+
+#[cfg(unix)]
+pub use self::unix::{serve, UnixSocketSigner};
+
+#[cfg(unix)]
+mod unix {
+    use std::io::{self, BufRead, BufReader, Read, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use super::{SignRequest, SignResponse, Signer};
+    use crate::error::{KError, KrakenErrors};
+
+    /// Delegates signing to a daemon listening on a local Unix socket
+    ///
+    /// See the [module documentation][super] for the protocol spoken with the daemon
+    #[derive(Debug)]
+    pub struct UnixSocketSigner {
+        path: PathBuf,
+        timeout: Duration,
+    }
+
+    impl UnixSocketSigner {
+        /// Default time allowed for the daemon to answer a request
+        pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+        /// Construct a new UnixSocketSigner sending requests to the daemon listening at `path`
+        pub fn new<P: AsRef<Path>>(path: P) -> Self {
+            UnixSocketSigner {
+                path: path.as_ref().to_path_buf(),
+                timeout: UnixSocketSigner::DEFAULT_TIMEOUT,
+            }
+        }
+
+        /// Set the time allowed for the daemon to answer each request
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+
+        fn request(&self, request: &SignRequest) -> io::Result<SignResponse> {
+            let mut stream = UnixStream::connect(&self.path)?;
+            stream.set_read_timeout(Some(self.timeout))?;
+            stream.set_write_timeout(Some(self.timeout))?;
+
+            let mut line = serde_json::to_vec(request)?;
+            line.push(b'\n');
+            stream.write_all(&line)?;
+
+            let mut response = String::new();
+            BufReader::new(stream).read_line(&mut response)?;
+            Ok(serde_json::from_str(&response)?)
+        }
+    }
+
+    impl Signer for UnixSocketSigner {
+        fn sign(
+            &self,
+            path: &str,
+            nonce: &str,
+            params: &str,
+        ) -> Result<String, KrakenErrors<KError>> {
+            let request = SignRequest {
+                path: path.to_string(),
+                nonce: nonce.to_string(),
+                params: params.to_string(),
+            };
+
+            match self.request(&request) {
+                Ok(SignResponse::Signature(signature)) => Ok(signature),
+                Ok(SignResponse::Error(err)) => Err(io::Error::other(err)),
+                Err(err) => Err(err),
+            }
+            .map_err(|err| KrakenErrors(vec![KError::SignerError(err)]))
+        }
+    }
+
+    // Time allowed for each read and write on a connection accepted by serve(), and the longest
+    // request line read. Connections are handled one at a time, so a client that stalls or
+    // never ends its line must not hold up the others
+    const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+    const MAX_REQUEST_SIZE: u64 = 1 << 20;
+
+    /// Answer signing requests sent by [UnixSocketSigner]s to `listener`, signing them with
+    /// `signer`
+    ///
+    /// Connections are handled one at a time until accepting a connection fails. A connection
+    /// that sends a malformed request, fails mid request or stalls for more than five seconds is
+    /// dropped without affecting the others
+    pub fn serve(listener: &UnixListener, signer: &dyn Signer) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept()?;
+            // Errors only concern this connection, and the client is told through its own
+            // failed read
+            let _ = handle(stream, signer);
+        }
+    }
+
+    fn handle(stream: UnixStream, signer: &dyn Signer) -> io::Result<()> {
+        stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+        stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+        let mut line = String::new();
+        BufReader::new((&stream).take(MAX_REQUEST_SIZE)).read_line(&mut line)?;
+
+        let response = match serde_json::from_str::<SignRequest>(&line) {
+            Ok(request) => match signer.sign(&request.path, &request.nonce, &request.params) {
+                Ok(signature) => SignResponse::Signature(signature),
+                Err(err) => SignResponse::Error(err.to_string()),
+            },
+            Err(err) => SignResponse::Error(format!("Malformed request: {}", err)),
+        };
+
+        let mut response = serde_json::to_vec(&response)?;
+        response.push(b'\n');
+        (&stream).write_all(&response)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::thread;
+    use std::time::Duration;
+
+    const SECRET: &str =
+        "FRs+gtq09rR7OFtKj9BGhyOGS3u5vtY/EdiIBO9kD8NFtRX7w7LeJDSrX6cq1D8zmQmGkWFjksuhBvKOAWJohQ==";
+
+    #[test]
+    fn unix_socket_signer() {
+        let path = env::temp_dir().join(format!("kraapi-signer-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        thread::spawn(move || serve(&listener, &HmacSigner::new(SECRET)));
+
+        // A client that connects and never sends its request only holds up the others until
+        // its connection times out
+        let _stalled = UnixStream::connect(&path).unwrap();
+
+        let signer = UnixSocketSigner::new(&path).with_timeout(Duration::from_secs(10));
+        let params = "nonce=1540973848000&asset=xbt";
+        assert_eq!(
+            signer
+                .sign("/0/private/TradeBalance", "1540973848000", params)
+                .unwrap(),
+            HmacSigner::new(SECRET)
+                .sign("/0/private/TradeBalance", "1540973848000", params)
+                .unwrap()
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,189 @@
+// Instrumentation of requests with the tracing crate, enabled by the tracing feature. Without the
+// feature the macros expand to nothing and RequestSpan does nothing, so call sites don't need to
+// be feature gated. Secrets are redacted here so every log line and Debug implementation shares
+// the same rules
+
+use hyper::header::HeaderMap;
+use indexmap::map::IndexMap;
+use std::fmt;
+use std::future::Future;
+
+use crate::api::{KrakenResult, MethodType};
+
+// Parameters holding secrets: the two factor password and the name of a withdrawal key
+const REDACTED_PARAMS: [&str; 2] = ["otp", "key"];
+// Headers holding the API key and the signature of the request
+const REDACTED_HEADERS: [&str; 2] = ["api-key", "api-sign"];
+const REDACTED: &str = "[REDACTED]";
+
+#[allow(unused_macros)]
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        {
+            ::tracing::trace!($($arg)*);
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        {
+            ::tracing::debug!($($arg)*);
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        {
+            ::tracing::warn!($($arg)*);
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {log_debug, log_trace, log_warn};
+
+// Request parameters formatted as a map with the secret values replaced
+pub(crate) struct RedactedParams<'a>(pub(crate) &'a IndexMap<String, String>);
+
+impl fmt::Debug for RedactedParams<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(
+                |(key, value)| match REDACTED_PARAMS.contains(&key.as_str()) {
+                    true => (key.as_str(), REDACTED),
+                    false => (key.as_str(), value.as_str()),
+                },
+            ))
+            .finish()
+    }
+}
+
+impl fmt::Display for RedactedParams<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+// Headers formatted as a map with the secret values replaced
+pub(crate) struct RedactedHeaders<'a>(pub(crate) &'a HeaderMap);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                match REDACTED_HEADERS.contains(&name.as_str()) {
+                    true => (name.as_str(), REDACTED),
+                    false => (name.as_str(), value.to_str().unwrap_or("[BINARY]")),
+                }
+            }))
+            .finish()
+    }
+}
+
+// Span covering one attempt at a request, from signing to parsing the response. Records the
+// endpoint, method and attempt up front, the http status once the response arrives and the
+// latency and any error codes Kraken returned once the response has been parsed
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl RequestSpan {
+    #[allow(unused_variables)]
+    pub(crate) fn new(endpoint: &str, method: MethodType, attempt: u32) -> Self {
+        RequestSpan {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "kraken_request",
+                endpoint,
+                method = %method,
+                attempt,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                errors = tracing::field::Empty,
+            ),
+            #[cfg(feature = "tracing")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    // Run `future` inside the span so the events it emits are attached to the request
+    pub(crate) async fn instrument<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+            future.instrument(self.span.clone()).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future.await
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub(crate) fn finish<R>(&self, result: &KrakenResult<R>) {
+        #[cfg(feature = "tracing")]
+        {
+            let latency = self.start.elapsed().as_secs_f64() * 1_000.0;
+            self.span.record("latency_ms", latency);
+            let _guard = self.span.enter();
+            match result {
+                Ok(_) => tracing::debug!(latency_ms = latency, "request completed"),
+                Err(errors) => {
+                    let codes = errors
+                        .0
+                        .iter()
+                        .map(|error| error.raw().map_or_else(|| error.to_string(), String::from))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    self.span.record("errors", codes.as_str());
+                    tracing::warn!(latency_ms = latency, errors = %codes, "request failed");
+                }
+            }
+        }
+    }
+}
+
+// Record the http status on the span of the request currently being made
+#[allow(unused_variables)]
+pub(crate) fn record_status(status: u16) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("status", status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    #[test]
+    fn redaction() {
+        let params = IndexMap::from([
+            (String::from("asset"), String::from("XBT")),
+            (String::from("key"), String::from("cold storage")),
+            (String::from("otp"), String::from("123456")),
+        ]);
+        assert_eq!(
+            format!("{:?}", RedactedParams(&params)),
+            r#"{"asset": "XBT", "key": "[REDACTED]", "otp": "[REDACTED]"}"#
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("API-Key", HeaderValue::from_static("my key"));
+        headers.insert("API-Sign", HeaderValue::from_static("signature"));
+        headers.insert("User-Agent", HeaderValue::from_static("kraapi"));
+        assert_eq!(
+            format!("{:?}", RedactedHeaders(&headers)),
+            r#"{"api-key": "[REDACTED]", "api-sign": "[REDACTED]", "user-agent": "kraapi"}"#
+        );
+    }
+}